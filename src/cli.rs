@@ -0,0 +1,79 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, Write};
+use std::path::Path;
+
+use fjall;
+
+use crate::config::Config;
+use crate::models::SettingsDB;
+use crate::repository::db_restore_backup;
+use crate::utils::SecretCipher;
+
+pub const USAGE: &str = "Usage:
+  dynamic_settings                              run the server
+  dynamic_settings backup <archive> [url]       download a backup from the running server, http://localhost:<port> by default
+  dynamic_settings restore <archive> [data-dir] load a backup into an empty data directory, `db` by default";
+
+/// Downloads the backup from the admin endpoint, so that the server does not have to be stopped
+pub async fn backup(config: &Config, archive: &Path, url: Option<&str>) -> io::Result<()> {
+    let url = match url {
+        Some(url) => url.trim_end_matches('/').to_string(),
+        None => format!("http://localhost:{}", config.application_port),
+    };
+    let mut response = reqwest::Client::new()
+        .get(format!("{}/api/v1/admin/backup", url))
+        .header("X-Api-Key", &config.admin_api_key)
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let message = response.text().await.unwrap_or_default();
+        return Err(io::Error::other(format!("Backup failed with {}: {}", status, message)));
+    }
+
+    let mut file = File::create(archive)?;
+    let written = async {
+        while let Some(chunk) = response.chunk().await.map_err(io::Error::other)? {
+            file.write_all(&chunk)?;
+        }
+        file.sync_all()
+    }.await;
+    // an incomplete archive must not be left behind
+    if written.is_err() {
+        let _ = fs::remove_file(archive);
+    }
+    written?;
+
+    println!("Backup written to {}", archive.display());
+    Ok(())
+}
+
+/// Loads the backup into a new keyspace, the server must not be running on `data_dir`.
+/// Secrets can be read only with the `SECRET_KEY` the backup was taken with.
+pub fn restore(config: &Config, archive: &Path, data_dir: &Path) -> io::Result<()> {
+    let is_empty = match fs::read_dir(data_dir) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => true,
+        Err(err) => return Err(err),
+    };
+    if !is_empty {
+        return Err(io::Error::other(format!("Data directory {} is not empty", data_dir.display())));
+    }
+
+    let keyspace = fjall::Config::new(data_dir)
+        .open_transactional()
+        .map_err(io::Error::other)?;
+    let db = SettingsDB::open(keyspace, SecretCipher::new(&config.secret_key))
+        .map_err(io::Error::other)?;
+
+    let manifest = db_restore_backup(&db, BufReader::new(File::open(archive)?))
+        .map_err(|err| io::Error::other(err.to_string()))?;
+    println!(
+        "Restored {} settings from the backup taken at {} into {}",
+        manifest.partitions.get("settings").copied().unwrap_or_default(),
+        manifest.created_at,
+        data_dir.display()
+    );
+    Ok(())
+}
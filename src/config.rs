@@ -2,13 +2,48 @@ use config::Environment;
 use dotenv::dotenv;
 use serde::Deserialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct Config {
     #[serde(default = "default_application_port")]
     pub application_port: u16,
 
     #[serde(default = "default_api_key")]
     pub api_key: String,
+
+    /// Required, there is no default: the admin key can reveal secrets and download backups
+    #[serde(default)]
+    pub admin_api_key: String,
+
+    /// Passphrase used to encrypt values of `secret` settings, required as well
+    #[serde(default)]
+    pub secret_key: String,
+
+    /// Comma separated key prefixes, changes to which require an approval
+    #[serde(default)]
+    pub protected_prefixes: String,
+
+    /// Deleted settings are kept in the trash for this many days before they are purged
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+}
+
+impl Config {
+    pub fn is_protected(&self, key: &str) -> bool {
+        self.protected_prefixes
+            .split(',')
+            .map(str::trim)
+            .any(|prefix| !prefix.is_empty() && key.starts_with(prefix))
+    }
+
+    /// Some of the keys under `prefix` may be protected
+    pub fn is_prefix_protected(&self, prefix: &str) -> bool {
+        self.protected_prefixes
+            .split(',')
+            .map(str::trim)
+            .any(|protected| {
+                !protected.is_empty() && (protected.starts_with(prefix) || prefix.starts_with(protected))
+            })
+    }
 }
 
 fn default_application_port() -> u16 {
@@ -19,6 +54,10 @@ fn default_api_key() -> String {
     "api-key".to_string()
 }
 
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
 pub fn get_config() -> Result<Config, config::ConfigError> {
     dotenv().ok();
 
@@ -26,5 +65,12 @@ pub fn get_config() -> Result<Config, config::ConfigError> {
 
     conf.merge(Environment::default().separator(""))?;
 
-    conf.try_into()
+    let config: Config = conf.try_into()?;
+    // a publicly known default would give anyone the admin scope and the key of the secrets
+    for (variable, value) in [("ADMIN_API_KEY", &config.admin_api_key), ("SECRET_KEY", &config.secret_key)] {
+        if value.trim().is_empty() {
+            return Err(config::ConfigError::Message(format!("`{}` is required", variable)));
+        }
+    }
+    Ok(config)
 }
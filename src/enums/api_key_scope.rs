@@ -0,0 +1,35 @@
+use std::future::{ready, Ready};
+
+use actix_web::{dev::Payload, FromRequest, HttpMessage, HttpRequest};
+
+use crate::errors::CustomError;
+
+/// Scope of the `X-Api-Key` the request was authorized with,
+/// put into request extensions by the auth middleware.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ApiKeyScope {
+    Regular,
+    Admin,
+}
+
+impl ApiKeyScope {
+    pub fn require_admin(&self) -> Result<(), CustomError> {
+        match self {
+            ApiKeyScope::Admin => Ok(()),
+            ApiKeyScope::Regular => Err(CustomError::ForbiddenError(
+                "admin `X-Api-Key` is required".to_string()
+            )),
+        }
+    }
+}
+
+impl FromRequest for ApiKeyScope {
+    type Error = CustomError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(req.extensions().get::<ApiKeyScope>().copied().ok_or_else(|| {
+            CustomError::UnauthorizedError("missing `X-Api-Key` header".to_string())
+        }))
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// What happened to a key of a bulk delete
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeleteOutcome {
+    Deleted,
+    NotFound,
+    Locked,
+    /// A change request was created for the protected settings
+    PendingApproval,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// What happens to a settings once its `expiresAt` is reached
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpireAction {
+    /// Revert to the value the settings had before the temporary override
+    Revert,
+    Delete,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FlagEvaluationReason {
+    /// One of the targeting rules matched the context
+    Rule,
+    /// No rule matched, the subject bucket was compared with the rollout
+    Rollout,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FlagRuleOperator {
+    Equals,
+    In,
+    SemverGte,
+    Regex,
+}
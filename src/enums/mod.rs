@@ -1,3 +1,17 @@
+mod api_key_scope;
+mod change_request;
+mod delete_outcome;
+mod expire_action;
+mod flag_evaluation_reason;
+mod flag_rule_operator;
+mod settings_sort;
 mod settings_value_type;
 
+pub use api_key_scope::ApiKeyScope;
+pub use change_request::{ChangeRequestKind, ChangeRequestStatus};
+pub use delete_outcome::DeleteOutcome;
+pub use expire_action::ExpireAction;
+pub use flag_evaluation_reason::FlagEvaluationReason;
+pub use flag_rule_operator::FlagRuleOperator;
+pub use settings_sort::SettingsSort;
 pub use settings_value_type::SettingsValueType;
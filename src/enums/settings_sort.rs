@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Order of the listed settings
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SettingsSort {
+    #[default]
+    Key,
+    /// Most recently updated first
+    UpdatedAt,
+}
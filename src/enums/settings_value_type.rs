@@ -2,14 +2,17 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum SettingsValueType {
+    #[default]
     Str,
     Int,
     Float,
     Bool,
-    Json
+    Json,
+    Secret,
+    Flag,
 }
 
 impl fmt::Display for SettingsValueType {
@@ -20,6 +23,8 @@ impl fmt::Display for SettingsValueType {
             SettingsValueType::Bool => "bool",
             SettingsValueType::Float => "float",
             SettingsValueType::Json => "json",
+            SettingsValueType::Secret => "secret",
+            SettingsValueType::Flag => "flag",
         };
         write!(f, "{}", as_str)
     }
@@ -33,6 +38,8 @@ impl From<String> for SettingsValueType {
             "bool" => SettingsValueType::Bool,
             "float" => SettingsValueType::Float,
             "json" => SettingsValueType::Json,
+            "secret" => SettingsValueType::Secret,
+            "flag" => SettingsValueType::Flag,
             // TODO: can forget to add a new type here
             _ => SettingsValueType::Str,
         }
@@ -13,6 +13,8 @@ pub enum CustomError {
     ForbiddenError(String),
     NotFoundError(String),
     ConflictError(String),
+    /// Stored data that can not be read back
+    CorruptDataError(String),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -37,6 +39,7 @@ impl ResponseError for CustomError {
             CustomError::ForbiddenError(_) => StatusCode::FORBIDDEN,
             CustomError::NotFoundError(_) => StatusCode::NOT_FOUND,
             CustomError::ConflictError(_) => StatusCode::CONFLICT,
+            CustomError::CorruptDataError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
@@ -50,6 +53,7 @@ impl ResponseError for CustomError {
             | CustomError::UnauthorizedError(message)
             | CustomError::ForbiddenError(message)
             | CustomError::ConflictError(message)
+            | CustomError::CorruptDataError(message)
             | CustomError::NotFoundError(message) => {
                 HttpResponse::build(status).body(ErrorResponse::to_json(message))
             }
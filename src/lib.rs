@@ -1,3 +1,4 @@
+pub mod cli;
 pub mod config;
 pub mod enums;
 pub mod errors;
@@ -6,3 +7,4 @@ pub mod models;
 pub mod repository;
 pub mod routes;
 pub mod startup;
+pub mod tasks;
@@ -1,28 +1,41 @@
 use std::net::TcpListener;
+use std::path::Path;
 
-use fjall::{Config, PartitionCreateOptions};
+use fjall::Config;
 
+use dynamic_settings::cli;
 use dynamic_settings::config::get_config;
 use dynamic_settings::models::SettingsDB;
 use dynamic_settings::startup::run;
+use dynamic_settings::utils::SecretCipher;
+
+const DATA_DIR: &str = "db";
 
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
     let config = get_config().expect("Failed to read configuration.");
 
-    let keyspace = Config::new("db")
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        [] => {}
+        ["backup", archive] => return cli::backup(&config, Path::new(archive), None).await,
+        ["backup", archive, url] => return cli::backup(&config, Path::new(archive), Some(url)).await,
+        ["restore", archive] => return cli::restore(&config, Path::new(archive), Path::new(DATA_DIR)),
+        ["restore", archive, data_dir] => return cli::restore(&config, Path::new(archive), Path::new(data_dir)),
+        _ => {
+            eprintln!("{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    }
+
+    let keyspace = Config::new(DATA_DIR)
         .open_transactional()
         .expect("Failed connect to keyspace");
-    let partition = keyspace
-        .open_partition("settings", PartitionCreateOptions::default())
+    let settings_db = SettingsDB::open(keyspace, SecretCipher::new(&config.secret_key))
         .expect("Failed to connect to partition");
 
-    let settings_db = SettingsDB {
-        keyspace,
-        partition,
-    };
-
     let address = format!("0.0.0.0:{}", config.application_port);
     let listener = TcpListener::bind(address)?;
-    run(listener, settings_db, config.api_key)?.await
+    run(listener, settings_db, config)?.await
 }
@@ -0,0 +1,29 @@
+use std::future::{ready, Ready};
+
+use actix_web::{dev::Payload, FromRequest, HttpMessage, HttpRequest};
+use sha2::{Digest, Sha256};
+
+use crate::errors::CustomError;
+
+/// Identifies the `X-Api-Key` a request was made with without storing the key itself,
+/// put into request extensions by the auth middleware.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiKeyFingerprint(pub String);
+
+impl ApiKeyFingerprint {
+    pub fn of(api_key: &str) -> Self {
+        let digest = Sha256::digest(api_key.as_bytes());
+        Self(digest[..8].iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+}
+
+impl FromRequest for ApiKeyFingerprint {
+    type Error = CustomError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(req.extensions().get::<ApiKeyFingerprint>().cloned().ok_or_else(|| {
+            CustomError::UnauthorizedError("missing `X-Api-Key` header".to_string())
+        }))
+    }
+}
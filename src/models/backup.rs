@@ -0,0 +1,16 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// First entry of a backup archive, followed by one `{partition}.bin` entry per partition
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BackupManifest {
+    /// `SETTINGS_FORMAT_VERSION` the settings were written in
+    #[serde(rename = "formatVersion")]
+    pub format_version: u8,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    /// Number of entries of every partition
+    pub partitions: BTreeMap<String, usize>,
+}
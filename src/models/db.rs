@@ -1,19 +1,55 @@
 use chrono::{DateTime, Utc};
-use fjall::{TxKeyspace, TxPartitionHandle, UserKey, UserValue};
+use fjall::{PartitionCreateOptions, TxKeyspace, TxPartitionHandle, UserKey, UserValue};
 use serde::{Deserialize, Serialize};
 
-use crate::enums::SettingsValueType;
-use crate::models::CreateSettingsRequest;
+use crate::enums::{ChangeRequestKind, ChangeRequestStatus, SettingsValueType};
+use crate::errors::CustomError;
+use crate::models::{CreateSettingsRequest, UpdateSettingsRequest};
+use crate::utils::SecretCipher;
 
+/// Value returned instead of the real value of `secret` settings
+pub const SECRET_MASK: &str = "********";
 
+#[derive(Clone)]
 pub struct SettingsDB {
     #[allow(unused)]
     pub keyspace: TxKeyspace,
 
     pub partition: TxPartitionHandle,
+
+    pub schedules: TxPartitionHandle,
+
+    pub change_requests: TxPartitionHandle,
+
+    pub trash: TxPartitionHandle,
+
+    /// Index of the expiring settings by `expires_at`, for the expiry sweeper
+    pub expirations: TxPartitionHandle,
+
+    pub cipher: SecretCipher,
 }
 
-#[derive(Serialize, Deserialize)]
+impl SettingsDB {
+    pub fn open(keyspace: TxKeyspace, cipher: SecretCipher) -> Result<Self, fjall::Error> {
+        let partition = keyspace.open_partition("settings", PartitionCreateOptions::default())?;
+        let schedules = keyspace.open_partition("schedules", PartitionCreateOptions::default())?;
+        let change_requests = keyspace.open_partition("change_requests", PartitionCreateOptions::default())?;
+        let trash = keyspace.open_partition("trash", PartitionCreateOptions::default())?;
+        let expirations = keyspace.open_partition("expirations", PartitionCreateOptions::default())?;
+
+        Ok(Self {
+            keyspace,
+            partition,
+            schedules,
+            change_requests,
+            trash,
+            expirations,
+            cipher,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct SettingsDBRow {
     pub key: String,
     pub value: String,
@@ -24,21 +60,71 @@ pub struct SettingsDBRow {
     pub created_at: DateTime<Utc>,
     #[serde(rename = "updatedAt")]
     pub updated_at: DateTime<Utc>,
+
+    // new fields are appended with `default`, so that rows written before them can still be read
+    #[serde(rename = "expiresAt", default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Value restored when the settings expires, without it the settings is deleted
+    #[serde(rename = "revertTo", default)]
+    pub revert_to: Option<SettingsRevert>,
+    /// Locked settings can not be updated or deleted until unlocked by an admin
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Incremented on every change of the value, `0` for the settings written before versions
+    #[serde(default)]
+    pub version: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SettingsRevert {
+    pub value: String,
+    #[serde(rename = "type")]
+    pub value_type: SettingsValueType,
+}
+
+impl SettingsDBRow {
+    pub fn masked(mut self) -> Self {
+        if self.value_type == SettingsValueType::Secret {
+            SECRET_MASK.clone_into(&mut self.value);
+        }
+        if let Some(revert_to) = self.revert_to.as_mut() {
+            if revert_to.value_type == SettingsValueType::Secret {
+                SECRET_MASK.clone_into(&mut revert_to.value);
+            }
+        }
+        self
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
 }
 
 impl From<&SettingsDBRow> for Vec<u8> {
     fn from(val: &SettingsDBRow) -> Self {
-        rmp_serde::to_vec(&val).expect("Error serializing settings to bytes")
+        val.encode()
     }
 }
 
-impl From<(UserKey, UserValue)> for SettingsDBRow {
-    fn from((key, value): (UserKey, UserValue)) -> Self {
-        let key = std::str::from_utf8(&key).unwrap();
-        let mut item: SettingsDBRow =
-            rmp_serde::from_slice(&value).expect("Error deserializing settings from bytes");
-        key.clone_into(&mut item.key);
-        item
+impl TryFrom<(UserKey, UserValue)> for SettingsDBRow {
+    type Error = CustomError;
+
+    fn try_from((key, value): (UserKey, UserValue)) -> Result<Self, Self::Error> {
+        let corrupt = |reason: String| CustomError::CorruptDataError(format!(
+            "Settings with key '{}' is corrupt: {}",
+            String::from_utf8_lossy(&key),
+            reason
+        ));
+        let key_str = std::str::from_utf8(&key).map_err(|err| corrupt(err.to_string()))?;
+        let mut item = SettingsDBRow::decode(&value).map_err(|err| corrupt(err.to_string()))?;
+        key_str.clone_into(&mut item.key);
+        Ok(item)
     }
 }
 
@@ -46,11 +132,157 @@ impl From<CreateSettingsRequest> for SettingsDBRow {
     fn from(request: CreateSettingsRequest) -> Self {
         let now = Utc::now();
         Self {
+            expires_at: request.expires_at(),
             key: request.key,
             value: request.value,
             value_type: request.value_type,
             created_at: now,
             updated_at: now,
+            revert_to: None,
+            locked: false,
+            description: request.description.filter(|description| !description.is_empty()),
+            owner: request.owner.filter(|owner| !owner.is_empty()),
+            tags: normalize_tags(request.tags),
+            version: 1,
         }
     }
 }
+
+/// Deleted settings kept in the trash until restored or purged
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TrashedSettingsDBRow {
+    pub settings: SettingsDBRow,
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashedSettingsDBRow {
+    pub fn masked(mut self) -> Self {
+        self.settings = self.settings.masked();
+        self
+    }
+}
+
+impl From<&TrashedSettingsDBRow> for Vec<u8> {
+    fn from(val: &TrashedSettingsDBRow) -> Self {
+        val.encode()
+    }
+}
+
+impl TryFrom<(UserKey, UserValue)> for TrashedSettingsDBRow {
+    type Error = CustomError;
+
+    fn try_from((key, value): (UserKey, UserValue)) -> Result<Self, Self::Error> {
+        TrashedSettingsDBRow::decode(&value).map_err(|err| CustomError::CorruptDataError(format!(
+            "Trashed settings with key '{}' is corrupt: {}",
+            String::from_utf8_lossy(&key),
+            err
+        )))
+    }
+}
+
+/// Trims the tags and drops the duplicates, keeping the order
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_string();
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// Settings change that is applied by the scheduler at `apply_at`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScheduledChangeDBRow {
+    pub id: String,
+    pub key: String,
+    pub value: String,
+    /// The value is encrypted, as it was scheduled for a `secret` settings
+    pub secret: bool,
+
+    #[serde(rename = "applyAt")]
+    pub apply_at: DateTime<Utc>,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ScheduledChangeDBRow {
+    pub fn masked(mut self) -> Self {
+        if self.secret {
+            SECRET_MASK.clone_into(&mut self.value);
+        }
+        self
+    }
+}
+
+impl From<&ScheduledChangeDBRow> for Vec<u8> {
+    fn from(val: &ScheduledChangeDBRow) -> Self {
+        rmp_serde::to_vec(&val).expect("Error serializing scheduled change to bytes")
+    }
+}
+
+impl TryFrom<(UserKey, UserValue)> for ScheduledChangeDBRow {
+    type Error = CustomError;
+
+    fn try_from((key, value): (UserKey, UserValue)) -> Result<Self, Self::Error> {
+        rmp_serde::from_slice(&value).map_err(|err| CustomError::CorruptDataError(format!(
+            "Scheduled change '{}' is corrupt: {}",
+            String::from_utf8_lossy(&key),
+            err
+        )))
+    }
+}
+
+/// Pending change to a settings under a protected prefix, applied once approved
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChangeRequestDBRow {
+    pub id: String,
+    pub key: String,
+    pub kind: ChangeRequestKind,
+    /// Only for `update` change requests
+    pub update: Option<UpdateSettingsRequest>,
+    /// The value of the update is encrypted, as it is for a `secret` settings
+    pub secret: bool,
+    pub status: ChangeRequestStatus,
+
+    #[serde(rename = "requestedBy")]
+    pub requested_by: String,
+    #[serde(rename = "resolvedBy")]
+    pub resolved_by: Option<String>,
+
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "resolvedAt")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl ChangeRequestDBRow {
+    pub fn masked(mut self) -> Self {
+        if let Some(value) = self.update.as_mut().and_then(|update| update.value.as_mut()) {
+            if self.secret {
+                SECRET_MASK.clone_into(value);
+            }
+        }
+        self
+    }
+}
+
+impl From<&ChangeRequestDBRow> for Vec<u8> {
+    fn from(val: &ChangeRequestDBRow) -> Self {
+        val.encode()
+    }
+}
+
+impl TryFrom<(UserKey, UserValue)> for ChangeRequestDBRow {
+    type Error = CustomError;
+
+    fn try_from((key, value): (UserKey, UserValue)) -> Result<Self, Self::Error> {
+        ChangeRequestDBRow::decode(&value).map_err(|err| CustomError::CorruptDataError(format!(
+            "Change request '{}' is corrupt: {}",
+            String::from_utf8_lossy(&key),
+            err
+        )))
+    }
+}
@@ -0,0 +1,198 @@
+use regex::{Regex, RegexBuilder};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::enums::{FlagEvaluationReason, FlagRuleOperator};
+use crate::errors::CustomError;
+use crate::utils::MAX_REGEX_SIZE;
+
+const MAX_ROLLOUT: u8 = 100;
+
+/// Value of `flag` settings stored as JSON, e.g.
+/// `{"rollout": 25, "rules": [{"attribute": "country", "operator": "in", "values": ["NL"], "result": true}]}`
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlagDefinition {
+    /// Percentage of subjects the flag is enabled for when no rule matches
+    pub rollout: u8,
+    /// Evaluated in order, the first matching rule wins
+    #[serde(default)]
+    pub rules: Vec<FlagRule>,
+    /// Makes the flag multivariate, weights should sum up to 100
+    #[serde(default)]
+    pub variants: Vec<FlagVariant>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlagRule {
+    /// Dot separated path in the evaluation context, e.g. `app.version`
+    pub attribute: String,
+    pub operator: FlagRuleOperator,
+    pub values: Vec<Value>,
+    pub result: bool,
+    /// Variant served when the rule matches, only for multivariate flags
+    #[serde(default)]
+    pub variant: Option<String>,
+    /// Pattern of a `regex` rule, compiled when the rule is validated
+    /// and reused for every context the parsed definition is evaluated with
+    #[serde(skip)]
+    regex: Option<Regex>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlagVariant {
+    pub name: String,
+    #[serde(default)]
+    pub payload: Value,
+    pub weight: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FlagEvaluation {
+    pub enabled: bool,
+    pub reason: FlagEvaluationReason,
+    #[serde(rename = "ruleIndex")]
+    pub rule_index: Option<usize>,
+    pub variant: Option<String>,
+    pub payload: Option<Value>,
+}
+
+impl FlagDefinition {
+    pub fn parse(value: &str) -> Result<Self, CustomError> {
+        let mut definition: FlagDefinition = serde_json::from_str(value).map_err(|err| {
+            CustomError::ValidationError(format!(
+                "Value '{}' is not a valid flag definition: {}",
+                value, err
+            ))
+        })?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    pub fn validate(&mut self) -> Result<(), CustomError> {
+        if self.rollout > MAX_ROLLOUT {
+            return Err(CustomError::ValidationError(format!(
+                "Flag rollout should be between 0 and {}",
+                MAX_ROLLOUT
+            )));
+        }
+        self.validate_variants()?;
+
+        for rule in &mut self.rules {
+            rule.validate()?;
+        }
+        for rule in &self.rules {
+            if let Some(variant) = &rule.variant {
+                if self.variant(variant).is_none() {
+                    return Err(CustomError::ValidationError(format!(
+                        "Rule for attribute '{}' refers to unknown variant '{}'",
+                        rule.attribute, variant
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&FlagVariant> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+
+    fn validate_variants(&self) -> Result<(), CustomError> {
+        if self.variants.is_empty() {
+            return Ok(());
+        }
+
+        for (index, variant) in self.variants.iter().enumerate() {
+            if variant.name.is_empty() {
+                return Err(CustomError::ValidationError(
+                    "Variant name should not be empty".to_string()
+                ));
+            }
+            if self.variants[..index].iter().any(|other| other.name == variant.name) {
+                return Err(CustomError::ValidationError(format!(
+                    "Variant '{}' is defined more than once",
+                    variant.name
+                )));
+            }
+        }
+
+        let total_weight: u32 = self.variants.iter().map(|variant| variant.weight as u32).sum();
+        if total_weight != MAX_ROLLOUT as u32 {
+            return Err(CustomError::ValidationError(format!(
+                "Variant weights should sum up to {}, got {}",
+                MAX_ROLLOUT, total_weight
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl FlagRule {
+    /// Also compiles the pattern of a `regex` rule. The definitions are parsed again for every evaluation request,
+    /// so the pattern is compiled once per request and flag, its size limit bounds the cost of each compilation.
+    pub fn validate(&mut self) -> Result<(), CustomError> {
+        let invalid = |message: &str| {
+            Err(CustomError::ValidationError(format!(
+                "Rule for attribute '{}' {}",
+                self.attribute, message
+            )))
+        };
+
+        match (&self.operator, self.values.as_slice()) {
+            (FlagRuleOperator::In, []) => invalid("should have at least one value"),
+            (FlagRuleOperator::In, _) => Ok(()),
+            (FlagRuleOperator::Equals, [_]) => Ok(()),
+            (FlagRuleOperator::SemverGte, [Value::String(version)]) => {
+                match Version::parse(version) {
+                    Ok(_) => Ok(()),
+                    Err(_) => invalid(&format!("has invalid semver '{}'", version)),
+                }
+            }
+            (FlagRuleOperator::Regex, [Value::String(pattern)]) => {
+                match RegexBuilder::new(pattern).size_limit(MAX_REGEX_SIZE).build() {
+                    Ok(regex) => {
+                        self.regex = Some(regex);
+                        Ok(())
+                    }
+                    Err(_) => invalid(&format!("has invalid regex '{}'", pattern)),
+                }
+            }
+            (FlagRuleOperator::SemverGte | FlagRuleOperator::Regex, _) => {
+                invalid("should have exactly one string value")
+            }
+            (FlagRuleOperator::Equals, _) => invalid("should have exactly one value"),
+        }
+    }
+
+    pub fn matches(&self, context: &Value) -> bool {
+        let Some(actual) = self.attribute
+            .split('.')
+            .try_fold(context, |value, part| value.get(part)) else {
+            return false;
+        };
+
+        match self.operator {
+            FlagRuleOperator::Equals => self.values.first() == Some(actual),
+            FlagRuleOperator::In => self.values.contains(actual),
+            FlagRuleOperator::SemverGte => {
+                let (Some(Value::String(expected)), Value::String(actual)) = (self.values.first(), actual) else {
+                    return false;
+                };
+                match (Version::parse(expected), Version::parse(actual)) {
+                    (Ok(expected), Ok(actual)) => actual >= expected,
+                    _ => false,
+                }
+            }
+            FlagRuleOperator::Regex => {
+                let (Some(regex), Value::String(actual)) = (&self.regex, actual) else {
+                    return false;
+                };
+                regex.is_match(actual)
+            }
+        }
+    }
+}
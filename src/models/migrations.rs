@@ -0,0 +1,143 @@
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::errors::CustomError;
+use crate::models::{ChangeRequestDBRow, SettingsDBRow, TrashedSettingsDBRow};
+
+/// Format of the stored `SettingsDBRow`, written as the first byte of the value.
+///
+/// Appending a field with `#[serde(default)]` keeps the format, old rows are read with the default.
+/// Any other change of the layout bumps the version and adds a migration from the previous one.
+pub const SETTINGS_FORMAT_VERSION: u8 = 1;
+
+/// Format of the stored `ChangeRequestDBRow`, versioned the same way as the settings.
+/// It covers the layout of the `UpdateSettingsRequest` the change request holds.
+pub const CHANGE_REQUEST_FORMAT_VERSION: u8 = 1;
+
+type Migration = fn(Vec<u8>) -> Result<Vec<u8>, CustomError>;
+
+/// `MIGRATIONS[n]` takes the payload written in format `n` and returns it in format `n + 1`
+const MIGRATIONS: [Migration; SETTINGS_FORMAT_VERSION as usize] = [
+    migrate_v0_to_v1,
+];
+
+const CHANGE_REQUEST_MIGRATIONS: [Migration; CHANGE_REQUEST_FORMAT_VERSION as usize] = [
+    migrate_v0_to_v1,
+];
+
+/// Format `0` are the rows written before the format byte: the payload is the same,
+/// the byte is only added in front of it
+fn migrate_v0_to_v1(payload: Vec<u8>) -> Result<Vec<u8>, CustomError> {
+    Ok(payload)
+}
+
+/// Rows written before the format byte are MessagePack arrays,
+/// so their first byte is an array marker, which is never used as a format version
+fn is_unversioned(bytes: &[u8]) -> bool {
+    matches!(bytes.first(), Some(0x90..=0x9f | 0xdc | 0xdd))
+}
+
+fn format_version(bytes: &[u8], name: &str) -> Result<u8, CustomError> {
+    if is_unversioned(bytes) {
+        return Ok(0);
+    }
+    bytes.first().copied().ok_or(CustomError::CorruptDataError(
+        format!("Stored {} is empty", name)
+    ))
+}
+
+/// Format the stored settings was written in
+pub fn settings_format_version(bytes: &[u8]) -> Result<u8, CustomError> {
+    format_version(bytes, "settings")
+}
+
+fn encode_versioned<T: Serialize>(row: &T, version: u8, name: &str) -> Vec<u8> {
+    let mut bytes = vec![version];
+    rmp_serde::encode::write(&mut bytes, row)
+        .unwrap_or_else(|_| panic!("Error serializing {} to bytes", name));
+    bytes
+}
+
+/// Reads the row written in the current or any older format, the older ones are migrated in memory
+fn decode_versioned<T: DeserializeOwned>(
+    bytes: &[u8],
+    version: u8,
+    migrations: &[Migration],
+    name: &str,
+) -> Result<T, CustomError> {
+    let written_version = format_version(bytes, name)?;
+    if written_version > version {
+        return Err(CustomError::CorruptDataError(format!(
+            "Format {} of the stored {} is newer than the supported {}",
+            written_version, name, version
+        )));
+    }
+
+    let mut payload = match written_version {
+        0 => bytes.to_vec(),
+        _ => bytes[1..].to_vec(),
+    };
+    for migration in &migrations[written_version as usize..] {
+        payload = migration(payload)?;
+    }
+
+    rmp_serde::from_slice(&payload).map_err(|err| CustomError::CorruptDataError(
+        format!("Error deserializing {} from bytes: {}", name, err)
+    ))
+}
+
+impl SettingsDBRow {
+    pub fn encode(&self) -> Vec<u8> {
+        encode_versioned(self, SETTINGS_FORMAT_VERSION, "settings")
+    }
+
+    /// Reads the settings written in the current or any older format,
+    /// the older ones are migrated in memory
+    pub fn decode(bytes: &[u8]) -> Result<Self, CustomError> {
+        decode_versioned(bytes, SETTINGS_FORMAT_VERSION, &MIGRATIONS, "settings")
+    }
+}
+
+impl ChangeRequestDBRow {
+    pub fn encode(&self) -> Vec<u8> {
+        encode_versioned(self, CHANGE_REQUEST_FORMAT_VERSION, "change request")
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, CustomError> {
+        decode_versioned(bytes, CHANGE_REQUEST_FORMAT_VERSION, &CHANGE_REQUEST_MIGRATIONS, "change request")
+    }
+}
+
+/// Trashed settings written before they were stored in the settings format
+#[derive(Deserialize)]
+struct UnversionedTrashedSettings {
+    settings: SettingsDBRow,
+    deleted_at: DateTime<Utc>,
+}
+
+impl TrashedSettingsDBRow {
+    /// `deleted_at` followed by the settings in the settings format,
+    /// so that the trashed settings are migrated as the others
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = rmp_serde::to_vec(&self.deleted_at).expect("Error serializing trashed settings to bytes");
+        bytes.extend(self.settings.encode());
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, CustomError> {
+        let corrupt = |err: &dyn std::fmt::Display| CustomError::CorruptDataError(
+            format!("Error deserializing trashed settings from bytes: {}", err)
+        );
+        if is_unversioned(bytes) {
+            let trashed: UnversionedTrashedSettings = rmp_serde::from_slice(bytes).map_err(|err| corrupt(&err))?;
+            return Ok(Self { settings: trashed.settings, deleted_at: trashed.deleted_at });
+        }
+
+        // the deserializer reads `deleted_at` only, leaving the settings in `rest`
+        let mut rest = bytes;
+        let deleted_at = DateTime::<Utc>::deserialize(&mut rmp_serde::Deserializer::new(&mut rest))
+            .map_err(|err| corrupt(&err))?;
+        let settings = SettingsDBRow::decode(rest)?;
+        Ok(Self { settings, deleted_at })
+    }
+}
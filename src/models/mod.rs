@@ -1,9 +1,17 @@
+mod api_key;
+mod backup;
 mod db;
+mod flag;
+mod migrations;
 mod request;
 mod response;
 mod query;
 
+pub use api_key::*;
+pub use backup::*;
 pub use db::*;
+pub use flag::*;
+pub use migrations::*;
 pub use request::*;
 pub use response::*;
 pub use query::*;
@@ -1,7 +1,148 @@
-use serde::Deserialize;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{ChangeRequestStatus, SettingsSort, SettingsValueType};
+use crate::errors::CustomError;
+use crate::models::SettingsDBRow;
 
 #[derive(Deserialize)]
 pub struct GetSettingsQueryParams {
     #[serde(default)]
     pub prefix: String,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Without `limit` and `cursor` the whole list is returned as a plain array, as before the pagination
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// `nextCursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub sort: SettingsSort,
+}
+
+const DEFAULT_LIST_LIMIT: usize = 100;
+const MAX_LIST_LIMIT: usize = 1_000;
+
+impl GetSettingsQueryParams {
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.limit.is_some_and(|limit| limit == 0 || limit > MAX_LIST_LIMIT) {
+            return Err(CustomError::ValidationError(format!(
+                "`limit` should be between 1 and {}",
+                MAX_LIST_LIMIT
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn is_paginated(&self) -> bool {
+        self.limit.is_some() || self.cursor.is_some()
+    }
+
+    pub fn page_limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIST_LIMIT)
+    }
+
+    pub fn matches(&self, settings: &SettingsDBRow) -> bool {
+        self.tag.as_ref().is_none_or(|tag| settings.tags.contains(tag))
+            && self.owner.as_ref().is_none_or(|owner| settings.owner.as_ref() == Some(owner))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CreateSettingsQueryParams {
+    /// Update the settings if it already exists instead of failing with a conflict
+    #[serde(default)]
+    pub upsert: bool,
+}
+
+/// Position after the last settings of a page, opaque for the clients
+#[derive(Serialize, Deserialize)]
+pub struct SettingsCursor {
+    pub key: String,
+    /// Only for the pages sorted by `updatedAt`
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Sort of the page the cursor was issued for, it only points into a list in the same order
+    pub sort: SettingsSort,
+}
+
+impl SettingsCursor {
+    pub fn encode(&self) -> String {
+        let bytes = rmp_serde::to_vec(self).expect("Error serializing cursor to bytes");
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, CustomError> {
+        URL_SAFE_NO_PAD.decode(cursor)
+            .ok()
+            .and_then(|bytes| rmp_serde::from_slice(&bytes).ok())
+            .ok_or(CustomError::ValidationError("Invalid `cursor`".to_string()))
+    }
+
+    pub fn validate(&self, sort: &SettingsSort) -> Result<(), CustomError> {
+        if self.sort != *sort {
+            return Err(CustomError::ValidationError(
+                "`cursor` was issued for a different `sort`".to_string()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DeleteSettingsByPrefixQueryParams {
+    pub prefix: String,
+    /// Number of settings the caller expects to delete, guards against a mistyped prefix
+    pub confirm: usize,
+}
+
+impl DeleteSettingsByPrefixQueryParams {
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.prefix.is_empty() {
+            return Err(CustomError::ValidationError("`prefix` should not be empty".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+pub struct GetChangeRequestsQueryParams {
+    #[serde(default)]
+    pub status: Option<ChangeRequestStatus>,
+}
+
+const MAX_SEARCH_LIMIT: usize = 1_000;
+
+fn default_search_limit() -> usize {
+    50
+}
+
+#[derive(Deserialize)]
+pub struct SearchSettingsQueryParams {
+    pub q: String,
+    /// `q` is a regex instead of a substring
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(rename = "type", default)]
+    pub value_type: Option<SettingsValueType>,
+    #[serde(default = "default_search_limit")]
+    pub limit: usize,
+}
+
+impl SearchSettingsQueryParams {
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.q.is_empty() {
+            return Err(CustomError::ValidationError("`q` should not be empty".to_string()));
+        }
+        if self.limit == 0 || self.limit > MAX_SEARCH_LIMIT {
+            return Err(CustomError::ValidationError(format!(
+                "`limit` should be between 1 and {}",
+                MAX_SEARCH_LIMIT
+            )));
+        }
+        Ok(())
+    }
 }
@@ -1,19 +1,36 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeDelta, Utc};
 use fjall::{UserKey, UserValue};
 use serde::{Deserialize, Serialize};
 
 use crate::errors::CustomError;
-use crate::enums::SettingsValueType;
+use crate::enums::{ExpireAction, SettingsValueType};
+use crate::models::SettingsDBRow;
 use crate::utils::validate_settings_value;
 
 const MAX_KEY_LENGTH: usize = 1_024;
 const MAX_VALUE_LENGTH: usize = 4 * 1_024 * 1_024; /* 4 MiB */
+const MAX_DESCRIPTION_LENGTH: usize = 4 * 1_024;
+const MAX_TAG_LENGTH: usize = 128;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct CreateSettingsRequest {
     pub key: String,
     pub value: String,
     #[serde(rename = "type")]
     pub value_type: SettingsValueType,
+    /// Seconds after which the settings is deleted
+    #[serde(default)]
+    pub ttl: Option<u64>,
+    #[serde(rename = "expiresAt", default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl From<&CreateSettingsRequest> for Vec<u8> {
@@ -33,6 +50,22 @@ impl From<(UserKey, UserValue)> for CreateSettingsRequest {
 }
 
 impl CreateSettingsRequest {
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        expiry_time(self.ttl, self.expires_at)
+    }
+
+    /// Update of the value, type and expiry of existing settings, for an upsert
+    pub fn as_update(&self) -> UpdateSettingsRequest {
+        UpdateSettingsRequest {
+            key: self.key.clone(),
+            value: Some(self.value.clone()),
+            value_type: Some(self.value_type.clone()),
+            ttl: self.ttl,
+            expires_at: self.expires_at,
+            ..Default::default()
+        }
+    }
+
     pub fn validate(&self) -> Result<(), CustomError> {
         if self.key.len() > MAX_KEY_LENGTH {
             return Err(CustomError::ValidationError(format!(
@@ -47,6 +80,8 @@ impl CreateSettingsRequest {
                 MAX_VALUE_LENGTH
             )));
         }
+        validate_expiry(self.ttl, self.expires_at)?;
+        validate_metadata(self.description.as_ref(), Some(&self.tags))?;
         validate_settings_value(self.value.clone(), self.value_type.clone())
     }
 }
@@ -54,6 +89,278 @@ impl CreateSettingsRequest {
 #[derive(Serialize, Deserialize)]
 pub struct DeleteSettingsByKeysRequest {
     pub keys: Vec<String>,
+    /// Fail without deleting anything if any of the keys is not found
+    #[serde(default)]
+    pub strict: bool,
+}
+
+const MAX_BATCH_KEYS: usize = 1_000;
+const MAX_TRANSACTION_OPERATIONS: usize = 100;
+
+#[derive(Serialize, Deserialize)]
+pub struct GetSettingsByKeysRequest {
+    pub keys: Vec<String>,
+}
+
+impl GetSettingsByKeysRequest {
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.keys.is_empty() || self.keys.len() > MAX_BATCH_KEYS {
+            return Err(CustomError::ValidationError(format!(
+                "`keys` should contain from 1 to {} keys",
+                MAX_BATCH_KEYS
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Adds `delta` to the current value of an `int` or `float` settings,
+/// the result should stay within the optional `min` and `max`
+#[derive(Serialize, Deserialize)]
+pub struct IncrementSettingsRequest {
+    pub delta: serde_json::Number,
+    #[serde(default)]
+    pub min: Option<serde_json::Number>,
+    #[serde(default)]
+    pub max: Option<serde_json::Number>,
+}
+
+impl IncrementSettingsRequest {
+    /// Returns the incremented value
+    pub fn apply(&self, settings: &SettingsDBRow) -> Result<String, CustomError> {
+        match settings.value_type {
+            SettingsValueType::Int => {
+                let integer = |number: &serde_json::Number, name: &str| number.as_i64().ok_or_else(|| {
+                    CustomError::ValidationError(format!("`{}` should be an integer for `int` settings", name))
+                });
+                let delta = integer(&self.delta, "delta")?;
+                let min = self.min.as_ref().map(|min| integer(min, "min")).transpose()?;
+                let max = self.max.as_ref().map(|max| integer(max, "max")).transpose()?;
+
+                let value = settings.value.parse::<i64>()
+                    .ok()
+                    .and_then(|value| value.checked_add(delta))
+                    .ok_or_else(|| CustomError::ValidationError(
+                        format!("Settings with key '{}' can not be incremented by {}", settings.key, delta)
+                    ))?;
+                check_bounds(&settings.key, value, min, max)?;
+                Ok(value.to_string())
+            }
+            SettingsValueType::Float => {
+                let float = |number: &serde_json::Number| number.as_f64().unwrap_or(f64::NAN);
+                let delta = float(&self.delta);
+                let value = settings.value.parse::<f64>()
+                    .ok()
+                    .map(|value| value + delta)
+                    .filter(|value| value.is_finite())
+                    .ok_or_else(|| CustomError::ValidationError(
+                        format!("Settings with key '{}' can not be incremented by {}", settings.key, delta)
+                    ))?;
+                check_bounds(&settings.key, value, self.min.as_ref().map(float), self.max.as_ref().map(float))?;
+                Ok(value.to_string())
+            }
+            _ => Err(CustomError::ValidationError(format!(
+                "Settings of type '{}' can not be incremented", settings.value_type
+            ))),
+        }
+    }
+}
+
+fn check_bounds<T: PartialOrd + std::fmt::Display>(
+    key: &str,
+    value: T,
+    min: Option<T>,
+    max: Option<T>,
+) -> Result<(), CustomError> {
+    if let Some(min) = min.filter(|min| value < *min) {
+        return Err(CustomError::ConflictError(format!(
+            "Settings with key '{}' would be {}, less than `min` {}", key, value, min
+        )));
+    }
+    if let Some(max) = max.filter(|max| value > *max) {
+        return Err(CustomError::ConflictError(format!(
+            "Settings with key '{}' would be {}, more than `max` {}", key, value, max
+        )));
+    }
+    Ok(())
+}
+
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+pub const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+
+/// Partial update of a `json` settings, chosen by the content type of the request
+pub enum SettingsPatch {
+    /// RFC 6902
+    Json(json_patch::Patch),
+    /// RFC 7396
+    Merge(serde_json::Value),
+}
+
+impl SettingsPatch {
+    pub fn parse(content_type: &str, body: &[u8]) -> Result<Self, CustomError> {
+        let invalid_body = |err: serde_json::Error| CustomError::ValidationError(
+            format!("Invalid patch: {}", err)
+        );
+        match content_type {
+            JSON_PATCH_CONTENT_TYPE => Ok(SettingsPatch::Json(
+                serde_json::from_slice(body).map_err(invalid_body)?
+            )),
+            MERGE_PATCH_CONTENT_TYPE => Ok(SettingsPatch::Merge(
+                serde_json::from_slice(body).map_err(invalid_body)?
+            )),
+            _ => Err(CustomError::ValidationError(format!(
+                "Content type should be '{}' or '{}'",
+                JSON_PATCH_CONTENT_TYPE, MERGE_PATCH_CONTENT_TYPE
+            ))),
+        }
+    }
+
+    /// Returns the patched value, only `json` settings and flag definitions can be patched.
+    /// The patched value is validated once again by the update.
+    pub fn apply(&self, settings: &SettingsDBRow) -> Result<String, CustomError> {
+        if !matches!(settings.value_type, SettingsValueType::Json | SettingsValueType::Flag) {
+            return Err(CustomError::ValidationError(format!(
+                "Settings of type '{}' can not be patched", settings.value_type
+            )));
+        }
+
+        let mut document: serde_json::Value = serde_json::from_str(&settings.value).map_err(|_| {
+            CustomError::ValidationError("Stored value is not a valid JSON".to_string())
+        })?;
+
+        match self {
+            SettingsPatch::Json(patch) => json_patch::patch(&mut document, patch).map_err(|err| {
+                CustomError::ValidationError(format!("Patch can not be applied: {}", err))
+            })?,
+            SettingsPatch::Merge(patch) => json_patch::merge(&mut document, patch),
+        }
+
+        let patched = document.to_string();
+        if patched.len() > MAX_VALUE_LENGTH {
+            return Err(CustomError::ValidationError(format!(
+                "Value length should be less than {} bytes",
+                MAX_VALUE_LENGTH
+            )));
+        }
+        Ok(patched)
+    }
+}
+
+/// Renames or copies a settings, or every settings under a prefix when `prefix` is set
+#[derive(Serialize, Deserialize)]
+pub struct MoveSettingsRequest {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub prefix: bool,
+}
+
+impl MoveSettingsRequest {
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.from.is_empty() || self.to.is_empty() {
+            return Err(CustomError::ValidationError(
+                "`from` and `to` should not be empty".to_string()
+            ));
+        }
+
+        if self.to.len() > MAX_KEY_LENGTH {
+            return Err(CustomError::ValidationError(format!(
+                "Key length should be less than {} bytes",
+                MAX_KEY_LENGTH
+            )));
+        }
+
+        // the moved settings would land among the ones being moved
+        if self.from == self.to
+            || (self.prefix && (self.to.starts_with(&self.from) || self.from.starts_with(&self.to))) {
+            return Err(CustomError::ValidationError(
+                "`from` and `to` should not overlap".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Destination key of a source key
+    pub fn destination(&self, key: &str) -> String {
+        format!("{}{}", self.to, &key[self.from.len()..])
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteSettingsOperation {
+    pub key: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum SettingsOperation {
+    Create(CreateSettingsRequest),
+    Update(UpdateSettingsRequest),
+    Delete(DeleteSettingsOperation),
+}
+
+impl SettingsOperation {
+    pub fn key(&self) -> &str {
+        match self {
+            SettingsOperation::Create(request) => &request.key,
+            SettingsOperation::Update(request) => &request.key,
+            SettingsOperation::Delete(request) => &request.key,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SettingsOperation::Create(_) => "create",
+            SettingsOperation::Update(_) => "update",
+            SettingsOperation::Delete(_) => "delete",
+        }
+    }
+}
+
+/// Operations applied all together or not at all
+#[derive(Serialize, Deserialize)]
+pub struct SettingsTransactionRequest {
+    pub operations: Vec<SettingsOperation>,
+    /// The transaction fails with a conflict unless every listed settings has the given version
+    #[serde(rename = "expectedVersions", default)]
+    pub expected_versions: HashMap<String, u64>,
+}
+
+impl SettingsTransactionRequest {
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.operations.is_empty() || self.operations.len() > MAX_TRANSACTION_OPERATIONS {
+            return Err(CustomError::ValidationError(format!(
+                "`operations` should contain from 1 to {} operations",
+                MAX_TRANSACTION_OPERATIONS
+            )));
+        }
+
+        for (i, operation) in self.operations.iter().enumerate() {
+            if self.operations[..i].iter().any(|other| other.key() == operation.key()) {
+                return Err(CustomError::ValidationError(format!(
+                    "Settings with key '{}' should be used only in one operation",
+                    operation.key()
+                )));
+            }
+
+            match operation {
+                SettingsOperation::Create(request) => request.validate()?,
+                SettingsOperation::Update(request) if request.dry_run => {
+                    return Err(CustomError::ValidationError(
+                        "`dryRun` is not supported in a transaction".to_string()
+                    ));
+                }
+                SettingsOperation::Update(request) => request.validate()?,
+                SettingsOperation::Delete(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RestoreSettingsRequest {
+    pub keys: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -61,13 +368,165 @@ pub struct ValidateTokenRequest {
     pub token: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct UpdateSettingsRequest {
     pub key: String,
-    pub value: String,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(rename = "type", default)]
+    pub value_type: Option<SettingsValueType>,
+    #[serde(rename = "dryRun", default)]
+    pub dry_run: bool,
+    /// Makes the update a temporary override that expires after `ttl` seconds
+    #[serde(default)]
+    pub ttl: Option<u64>,
+    #[serde(rename = "expiresAt", default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// `revert` by default
+    #[serde(rename = "onExpire", default)]
+    pub on_expire: Option<ExpireAction>,
 }
 
 impl UpdateSettingsRequest {
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        expiry_time(self.ttl, self.expires_at)
+    }
+
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.value.is_none() && self.value_type.is_none() {
+            return Err(CustomError::ValidationError(
+                "Either `value` or `type` should be provided".to_string()
+            ));
+        }
+
+        if self.dry_run && self.value_type.is_none() {
+            return Err(CustomError::ValidationError(
+                "`dryRun` requires `type` to be provided".to_string()
+            ));
+        }
+
+        if self.on_expire.is_some() && self.ttl.is_none() && self.expires_at.is_none() {
+            return Err(CustomError::ValidationError(
+                "`onExpire` requires `ttl` or `expiresAt` to be provided".to_string()
+            ));
+        }
+
+        if let Some(value) = &self.value {
+            if value.len() > MAX_VALUE_LENGTH {
+                return Err(CustomError::ValidationError(format!(
+                    "Value length should be less than {} bytes",
+                    MAX_VALUE_LENGTH
+                )));
+            }
+        }
+        validate_expiry(self.ttl, self.expires_at)
+    }
+}
+
+/// Edits only the metadata of the settings, omitted fields are left as they are
+/// and an empty `description` or `owner` clears it
+#[derive(Serialize, Deserialize)]
+pub struct UpdateSettingsMetadataRequest {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+}
+
+impl UpdateSettingsMetadataRequest {
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.description.is_none() && self.owner.is_none() && self.tags.is_none() {
+            return Err(CustomError::ValidationError(
+                "Either `description`, `owner` or `tags` should be provided".to_string()
+            ));
+        }
+        validate_metadata(self.description.as_ref(), self.tags.as_ref())
+    }
+}
+
+fn validate_metadata(
+    description: Option<&String>,
+    tags: Option<&Vec<String>>,
+) -> Result<(), CustomError> {
+    if description.is_some_and(|description| description.len() > MAX_DESCRIPTION_LENGTH) {
+        return Err(CustomError::ValidationError(format!(
+            "Description length should be less than {} bytes",
+            MAX_DESCRIPTION_LENGTH
+        )));
+    }
+
+    for tag in tags.into_iter().flatten() {
+        if tag.trim().is_empty() {
+            return Err(CustomError::ValidationError("Tags should not be empty".to_string()));
+        }
+        if tag.len() > MAX_TAG_LENGTH {
+            return Err(CustomError::ValidationError(format!(
+                "Tag length should be less than {} bytes",
+                MAX_TAG_LENGTH
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_expiry(
+    ttl: Option<u64>,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<(), CustomError> {
+    match (ttl, expires_at) {
+        (Some(_), Some(_)) => Err(CustomError::ValidationError(
+            "Only one of `ttl` and `expiresAt` should be provided".to_string()
+        )),
+        (Some(0), None) => Err(CustomError::ValidationError(
+            "`ttl` should be greater than 0".to_string()
+        )),
+        (Some(ttl), None) if expiry_time(Some(ttl), None).is_none() => {
+            Err(CustomError::ValidationError("`ttl` is too big".to_string()))
+        }
+        (None, Some(expires_at)) if expires_at <= Utc::now() => Err(CustomError::ValidationError(
+            "`expiresAt` should be in the future".to_string()
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn expiry_time(
+    ttl: Option<u64>,
+    expires_at: Option<DateTime<Utc>>,
+) -> Option<DateTime<Utc>> {
+    expires_at.or_else(|| {
+        let ttl = TimeDelta::try_seconds(i64::try_from(ttl?).ok()?)?;
+        Utc::now().checked_add_signed(ttl)
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EvaluateFlagRequest {
+    pub subject: String,
+    /// Attributes the targeting rules are matched against
+    #[serde(default)]
+    pub context: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EvaluateFlagsRequest {
+    #[serde(default)]
+    pub prefix: String,
+    pub subject: String,
+    #[serde(default)]
+    pub context: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScheduleSettingsRequest {
+    pub value: String,
+    #[serde(rename = "applyAt")]
+    pub apply_at: DateTime<Utc>,
+}
+
+impl ScheduleSettingsRequest {
     pub fn validate(&self) -> Result<(), CustomError> {
         if self.value.len() > MAX_VALUE_LENGTH {
             return Err(CustomError::ValidationError(format!(
@@ -75,6 +534,12 @@ impl UpdateSettingsRequest {
                 MAX_VALUE_LENGTH
             )));
         }
+
+        if self.apply_at <= Utc::now() {
+            return Err(CustomError::ValidationError(
+                "`applyAt` should be in the future".to_string()
+            ));
+        }
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,120 @@
 use serde::{Deserialize, Serialize};
 
+use crate::enums::{DeleteOutcome, SettingsValueType};
+use crate::models::{ChangeRequestDBRow, FlagEvaluation, SettingsDBRow};
+
 #[derive(Serialize, Deserialize)]
 pub struct MessageResponse {
     pub message: String,
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct UpdateSettingsDryRunResponse {
+    pub key: String,
+    pub from: SettingsValueType,
+    pub to: SettingsValueType,
+    pub convertible: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FlagEvaluationResponse {
+    pub key: String,
+    #[serde(flatten)]
+    pub evaluation: FlagEvaluation,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FlagsEvaluationResponse {
+    pub items: Vec<FlagEvaluationResponse>,
+    /// Settings under the prefix that could not be read
+    #[serde(rename = "corruptKeys", default, skip_serializing_if = "Vec::is_empty")]
+    pub corrupt_keys: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteSettingsResult {
+    pub key: String,
+    pub outcome: DeleteOutcome,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteSettingsResponse {
+    pub message: String,
+    pub results: Vec<DeleteSettingsResult>,
+    /// Created for the protected settings
+    #[serde(rename = "changeRequests", default, skip_serializing_if = "Vec::is_empty")]
+    pub change_requests: Vec<ChangeRequestDBRow>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SettingsPageResponse {
+    pub items: Vec<SettingsDBRow>,
+    /// `None` on the last page
+    #[serde(rename = "nextCursor")]
+    pub next_cursor: Option<String>,
+    /// Settings of the page that could not be read
+    #[serde(rename = "corruptKeys", default, skip_serializing_if = "Vec::is_empty")]
+    pub corrupt_keys: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SearchSettingsResponse {
+    pub items: Vec<SettingsDBRow>,
+    /// Settings that could not be read, so could not be searched
+    #[serde(rename = "corruptKeys", default, skip_serializing_if = "Vec::is_empty")]
+    pub corrupt_keys: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetSettingsByKeysResponse {
+    pub found: Vec<SettingsDBRow>,
+    pub missing: Vec<String>,
+    #[serde(rename = "corruptKeys", default, skip_serializing_if = "Vec::is_empty")]
+    pub corrupt_keys: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SettingsOperationResult {
+    pub op: String,
+    pub key: String,
+    /// Version after the operation, `None` for `delete`
+    pub version: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SettingsTransactionResponse {
+    pub results: Vec<SettingsOperationResult>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MovedSettings {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MoveSettingsResponse {
+    pub message: String,
+    pub keys: Vec<MovedSettings>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RestoreSettingsResponse {
+    pub message: String,
+    pub restored: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CorruptSettings {
+    pub key: String,
+    /// Why the stored settings can not be read
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IntegrityReportResponse {
+    /// Number of settings scanned
+    pub checked: usize,
+    pub corrupt: Vec<CorruptSettings>,
+}
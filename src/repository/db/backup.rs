@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use chrono::Utc;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use fjall::{self, TxPartitionHandle, UserKey, UserValue};
+
+use crate::errors::CustomError;
+use crate::models::{BackupManifest, SettingsDB, SETTINGS_FORMAT_VERSION};
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+
+/// Every partition of the keyspace, by the name it is opened with
+fn partitions(db: &SettingsDB) -> [(&'static str, &TxPartitionHandle); 4] {
+    [
+        ("settings", &db.partition),
+        ("schedules", &db.schedules),
+        ("change_requests", &db.change_requests),
+        ("trash", &db.trash),
+    ]
+}
+
+fn entry_name(partition: &str) -> String {
+    format!("{}.bin", partition)
+}
+
+fn write_error(err: std::io::Error) -> CustomError {
+    CustomError::InternalError(format!("Error writing backup: {}", err))
+}
+
+fn invalid_backup(reason: impl std::fmt::Display) -> CustomError {
+    CustomError::ValidationError(format!("Invalid backup archive: {}", reason))
+}
+
+/// Size of a partition entry: every key and value is prefixed with its length
+const LENGTH_PREFIX_SIZE: u64 = 4;
+
+/// Writes a tar.gz archive of all the partitions read from one snapshot,
+/// so the backup is consistent while the server keeps serving writes.
+/// The snapshot is read twice: first for the sizes of the entries, then the items are written
+/// one at a time, so the partitions are never held in memory.
+/// The values are copied as they are stored: secrets stay encrypted with the current secret key.
+pub fn db_write_backup<W: Write>(
+    db: &SettingsDB,
+    writer: W,
+) -> Result<BackupManifest, CustomError> {
+    let read_tx = db.keyspace.read_tx();
+    let created_at = Utc::now();
+
+    let mut manifest = BackupManifest {
+        format_version: SETTINGS_FORMAT_VERSION,
+        created_at,
+        partitions: BTreeMap::new(),
+    };
+    let mut sizes = Vec::new();
+    for (name, partition) in partitions(db) {
+        let mut count = 0;
+        let mut size = 0;
+        for item in read_tx.iter(partition) {
+            let (key, value) = item?;
+            size += 2 * LENGTH_PREFIX_SIZE + key.len() as u64 + value.len() as u64;
+            count += 1;
+        }
+        manifest.partitions.insert(name.to_string(), count);
+        sizes.push(size);
+    }
+    let manifest_json = serde_json::to_vec_pretty(&manifest).expect("Error serializing backup manifest");
+
+    let mut archive = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+    let mut append = |name: &str, size: u64, data: &mut dyn Read| {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(size);
+        header.set_mode(0o644);
+        header.set_mtime(created_at.timestamp() as u64);
+        header.set_cksum();
+        archive.append_data(&mut header, name, data).map_err(write_error)
+    };
+    append(MANIFEST_ENTRY, manifest_json.len() as u64, &mut manifest_json.as_slice())?;
+    for ((name, partition), size) in partitions(db).into_iter().zip(sizes) {
+        let mut items = PartitionReader {
+            items: read_tx.iter(partition),
+            pending: Vec::new(),
+            position: 0,
+        };
+        append(&entry_name(name), size, &mut items)?;
+    }
+    archive.into_inner().and_then(|encoder| encoder.finish()).map_err(write_error)?;
+    Ok(manifest)
+}
+
+/// Reads the length-prefixed keys and values of a partition, one item at a time
+struct PartitionReader<I> {
+    items: I,
+    pending: Vec<u8>,
+    position: usize,
+}
+
+impl<I: Iterator<Item = fjall::Result<(UserKey, UserValue)>>> Read for PartitionReader<I> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.position == self.pending.len() {
+            let Some(item) = self.items.next() else {
+                return Ok(0);
+            };
+            let (key, value) = item.map_err(std::io::Error::other)?;
+            self.pending.clear();
+            self.position = 0;
+            for bytes in [&key[..], &value[..]] {
+                self.pending.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+                self.pending.extend_from_slice(bytes);
+            }
+        }
+
+        let read = buf.len().min(self.pending.len() - self.position);
+        buf[..read].copy_from_slice(&self.pending[self.position..self.position + read]);
+        self.position += read;
+        Ok(read)
+    }
+}
+
+/// Loads an archive written by `db_write_backup` in one transaction,
+/// only into a keyspace with no data, so that nothing is overwritten.
+pub fn db_restore_backup<R: Read>(
+    db: &SettingsDB,
+    reader: R,
+) -> Result<BackupManifest, CustomError> {
+    let read_tx = db.keyspace.read_tx();
+    for (name, partition) in partitions(db) {
+        if !read_tx.is_empty(partition)? {
+            return Err(CustomError::ConflictError(
+                format!("Backup can only be restored into an empty data directory, '{}' has data", name)
+            ));
+        }
+    }
+
+    let mut manifest: Option<BackupManifest> = None;
+    let mut entries: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+    for entry in archive.entries().map_err(invalid_backup)? {
+        let mut entry = entry.map_err(invalid_backup)?;
+        let name = entry.path().map_err(invalid_backup)?.to_string_lossy().into_owned();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).map_err(invalid_backup)?;
+        match name.as_str() {
+            MANIFEST_ENTRY => manifest = Some(serde_json::from_slice(&data).map_err(invalid_backup)?),
+            _ => {
+                entries.insert(name, data);
+            }
+        }
+    }
+
+    let Some(manifest) = manifest else {
+        return Err(invalid_backup(format!("no '{}'", MANIFEST_ENTRY)));
+    };
+    if manifest.format_version > SETTINGS_FORMAT_VERSION {
+        return Err(invalid_backup(format!(
+            "settings format {} is newer than the supported {}",
+            manifest.format_version, SETTINGS_FORMAT_VERSION
+        )));
+    }
+
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+    for (name, partition) in partitions(db) {
+        let data = entries.remove(&entry_name(name)).unwrap_or_default();
+        let items = read_items(&data).ok_or(invalid_backup(format!("'{}' is truncated", entry_name(name))))?;
+        if Some(&items.len()) != manifest.partitions.get(name) {
+            return Err(invalid_backup(format!("'{}' does not match the manifest", entry_name(name))));
+        }
+        for (key, value) in items {
+            write_tx.insert(partition, key, value);
+        }
+    }
+    write_tx.commit()?;
+    Ok(manifest)
+}
+
+/// Splits the length-prefixed keys and values, `None` if the data ends in the middle of one
+fn read_items(mut data: &[u8]) -> Option<Vec<(&[u8], &[u8])>> {
+    let mut items = Vec::new();
+    while !data.is_empty() {
+        let key = read_bytes(&mut data)?;
+        let value = read_bytes(&mut data)?;
+        items.push((key, value));
+    }
+    Some(items)
+}
+
+fn read_bytes<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let (len, rest) = data.split_first_chunk::<4>()?;
+    let len = u32::from_be_bytes(*len) as usize;
+    if rest.len() < len {
+        return None;
+    }
+    let (bytes, rest) = rest.split_at(len);
+    *data = rest;
+    Some(bytes)
+}
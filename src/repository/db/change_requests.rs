@@ -0,0 +1,206 @@
+use fjall::{self, WriteTransaction};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::enums::{ChangeRequestKind, ChangeRequestStatus, SettingsValueType};
+use crate::errors::CustomError;
+use crate::models::{ChangeRequestDBRow, SettingsDB, SettingsDBRow, UpdateSettingsRequest};
+use crate::repository::db_get_settings_by_key;
+use super::settings::{locked_error, update_settings_in_tx, validate_settings_update};
+use super::trash::trash_settings_in_tx;
+
+pub fn db_create_change_request(
+    db: &SettingsDB,
+    key: &str,
+    update: Option<UpdateSettingsRequest>,
+    requested_by: &str,
+) -> Result<Option<ChangeRequestDBRow>, CustomError> {
+    let Some(settings) = db_get_settings_by_key(db, key)? else {
+        return Ok(None);
+    };
+    // it could not be approved anyway
+    if settings.locked {
+        return Err(locked_error(&settings.key));
+    }
+
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+    let change_request = create_change_request_in_tx(db, &mut write_tx, &settings, update, requested_by)?;
+    write_tx.commit()?;
+
+    Ok(Some(change_request))
+}
+
+/// Validates the change against the current settings and writes the pending change request
+/// into the given transaction without committing it
+pub(crate) fn create_change_request_in_tx(
+    db: &SettingsDB,
+    write_tx: &mut WriteTransaction,
+    settings: &SettingsDBRow,
+    update: Option<UpdateSettingsRequest>,
+    requested_by: &str,
+) -> Result<ChangeRequestDBRow, CustomError> {
+    let (kind, secret, update) = match update {
+        Some(mut update) => {
+            // validated against the current settings now, and once again when approved
+            let value_type = update.value_type.clone().unwrap_or(settings.value_type.clone());
+            validate_settings_update(db, settings, update.value.as_ref(), &value_type)?;
+
+            let secret = settings.value_type == SettingsValueType::Secret
+                || value_type == SettingsValueType::Secret;
+            if let (true, Some(value)) = (secret, update.value.as_ref()) {
+                update.value = Some(db.cipher.encrypt(value)?);
+            }
+            (ChangeRequestKind::Update, secret, Some(update))
+        }
+        None => (ChangeRequestKind::Delete, false, None),
+    };
+
+    let change_request = ChangeRequestDBRow {
+        id: Uuid::new_v4().to_string(),
+        key: settings.key.clone(),
+        kind,
+        update,
+        secret,
+        status: ChangeRequestStatus::Pending,
+        requested_by: requested_by.to_string(),
+        resolved_by: None,
+        created_at: Utc::now(),
+        resolved_at: None,
+    };
+
+    let serialized: Vec<u8> = (&change_request).into();
+    write_tx.insert(&db.change_requests, &change_request.id, serialized);
+    Ok(change_request)
+}
+
+/// Corrupt change requests are logged and skipped
+pub fn db_get_change_requests(
+    db: &SettingsDB,
+    status: Option<&ChangeRequestStatus>,
+) -> Result<Vec<ChangeRequestDBRow>, CustomError> {
+    let read_tx = db.keyspace.read_tx();
+    let mut change_requests = Vec::new();
+    for item in read_tx.iter(&db.change_requests) {
+        match ChangeRequestDBRow::try_from(item?) {
+            Ok(change_request) if status.is_none_or(|status| change_request.status == *status) => {
+                change_requests.push(change_request);
+            }
+            Ok(_) => {}
+            Err(err) => log::error!("{}", err),
+        }
+    }
+
+    change_requests.sort_by_key(|change_request| change_request.created_at);
+    Ok(change_requests)
+}
+
+/// Applies the change and marks it approved in one transaction, so that concurrent approvals apply it only once.
+/// The change request should be approved with a different api key than it was requested with.
+pub fn db_approve_change_request(
+    db: &SettingsDB,
+    id: &str,
+    approved_by: &str,
+) -> Result<Option<ChangeRequestDBRow>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    let Some(item) = write_tx.get(&db.change_requests, id)? else {
+        return Ok(None);
+    };
+    let change_request = ChangeRequestDBRow::try_from((id.into(), item))?;
+    ensure_pending(&change_request)?;
+
+    if change_request.requested_by == approved_by {
+        return Err(CustomError::ForbiddenError(
+            "Change request should be approved with a different api key".to_string()
+        ));
+    }
+
+    let not_found = || CustomError::NotFoundError(
+        format!("Settings with key '{}' not found", change_request.key)
+    );
+    match (&change_request.kind, change_request.update.clone()) {
+        (ChangeRequestKind::Update, Some(mut update)) => {
+            if let (true, Some(value)) = (change_request.secret, update.value.as_ref()) {
+                update.value = Some(db.cipher.decrypt(value)?);
+            }
+            if update_settings_in_tx(db, &mut write_tx, &update)?.is_none() {
+                return Err(not_found());
+            }
+        }
+        (ChangeRequestKind::Delete, _) => {
+            let Some(item) = write_tx.get(&db.partition, &change_request.key)? else {
+                return Err(not_found());
+            };
+            let settings = SettingsDBRow::try_from((change_request.key.as_str().into(), item))?;
+            if settings.locked {
+                return Err(locked_error(&settings.key));
+            }
+            trash_settings_in_tx(db, &mut write_tx, settings);
+        }
+        (ChangeRequestKind::Update, None) => {
+            return Err(CustomError::InternalError(
+                format!("Change request '{}' has no update", id)
+            ));
+        }
+    }
+
+    let change_request = resolve_change_request_in_tx(
+        db, &mut write_tx, change_request, ChangeRequestStatus::Approved, approved_by
+    );
+    write_tx.commit()?;
+    Ok(Some(change_request))
+}
+
+pub fn db_reject_change_request(
+    db: &SettingsDB,
+    id: &str,
+    rejected_by: &str,
+) -> Result<Option<ChangeRequestDBRow>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    let Some(item) = write_tx.get(&db.change_requests, id)? else {
+        return Ok(None);
+    };
+    let change_request = ChangeRequestDBRow::try_from((id.into(), item))?;
+    ensure_pending(&change_request)?;
+
+    let change_request = resolve_change_request_in_tx(
+        db, &mut write_tx, change_request, ChangeRequestStatus::Rejected, rejected_by
+    );
+    write_tx.commit()?;
+    Ok(Some(change_request))
+}
+
+fn resolve_change_request_in_tx(
+    db: &SettingsDB,
+    write_tx: &mut WriteTransaction,
+    change_request: ChangeRequestDBRow,
+    status: ChangeRequestStatus,
+    resolved_by: &str,
+) -> ChangeRequestDBRow {
+    let change_request = ChangeRequestDBRow {
+        status,
+        resolved_by: Some(resolved_by.to_string()),
+        resolved_at: Some(Utc::now()),
+        ..change_request
+    };
+    let serialized: Vec<u8> = (&change_request).into();
+
+    write_tx.insert(&db.change_requests, &change_request.id, serialized);
+    change_request
+}
+
+fn ensure_pending(change_request: &ChangeRequestDBRow) -> Result<(), CustomError> {
+    if change_request.status != ChangeRequestStatus::Pending {
+        return Err(CustomError::ConflictError(format!(
+            "Change request '{}' is not pending", change_request.id
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use fjall::{self, UserKey, WriteTransaction};
+
+use crate::errors::CustomError;
+use crate::models::{SettingsDB, SettingsDBRow};
+
+/// Microseconds with the sign bit flipped, big-endian, so that the bytes sort by time
+fn timestamp_bytes(at: DateTime<Utc>) -> [u8; 8] {
+    ((at.timestamp_micros() as u64) ^ (1 << 63)).to_be_bytes()
+}
+
+/// Key of the `expirations` index: the expiry followed by the key of the settings
+fn expiration_key(expires_at: DateTime<Utc>, key: &str) -> Vec<u8> {
+    let mut index_key = timestamp_bytes(expires_at).to_vec();
+    index_key.extend_from_slice(key.as_bytes());
+    index_key
+}
+
+/// Adds an expiring settings to the index. The entries are never updated in place:
+/// the one of an expiry the settings no longer has is dropped by the sweeper when it is due.
+pub(crate) fn index_expiration(
+    db: &SettingsDB,
+    write_tx: &mut WriteTransaction,
+    settings: &SettingsDBRow,
+) {
+    if let Some(expires_at) = settings.expires_at {
+        write_tx.insert(&db.expirations, expiration_key(expires_at, &settings.key), []);
+    }
+}
+
+/// Indexes every expiring settings, for the ones written before the index or restored from a backup,
+/// returns how many are indexed
+pub fn db_index_expirations(db: &SettingsDB) -> Result<usize, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    let mut expiring = Vec::new();
+    for item in write_tx.iter(&db.partition) {
+        // corrupt settings are left as they are, to be found by the integrity check
+        match SettingsDBRow::try_from(item?) {
+            Ok(settings) if settings.expires_at.is_some() => expiring.push(settings),
+            Ok(_) => {}
+            Err(err) => log::error!("{}", err),
+        }
+    }
+
+    for settings in &expiring {
+        index_expiration(db, &mut write_tx, settings);
+    }
+    write_tx.commit()?;
+    Ok(expiring.len())
+}
+
+/// Index entries up to `now` with the keys of their settings, read with one range scan
+pub(crate) fn due_expirations(
+    db: &SettingsDB,
+    now: DateTime<Utc>,
+) -> Result<Vec<(UserKey, String)>, CustomError> {
+    let end = timestamp_bytes(now + chrono::Duration::microseconds(1));
+    let mut due = Vec::new();
+    for item in db.keyspace.read_tx().range(&db.expirations, ..end.as_slice()) {
+        let (index_key, _) = item?;
+        let key = String::from_utf8_lossy(&index_key[end.len()..]).into_owned();
+        due.push((index_key, key));
+    }
+    Ok(due)
+}
+
+/// Whether the index entry is the one of the current expiry of the settings
+pub(crate) fn is_current_expiration(index_key: &[u8], settings: &SettingsDBRow) -> bool {
+    settings.expires_at.is_some_and(|expires_at| *index_key == *expiration_key(expires_at, &settings.key))
+}
@@ -0,0 +1,20 @@
+use crate::errors::CustomError;
+use crate::models::{CorruptSettings, IntegrityReportResponse, SettingsDB, SettingsDBRow};
+
+/// Reads every settings of the partition and reports the ones that can not be read back
+pub fn db_check_integrity(
+    db: &SettingsDB,
+) -> Result<IntegrityReportResponse, CustomError> {
+    let read_tx = db.keyspace.read_tx();
+
+    let mut report = IntegrityReportResponse { checked: 0, corrupt: Vec::new() };
+    for item in read_tx.iter(&db.partition) {
+        let item = item?;
+        let key = String::from_utf8_lossy(&item.0).into_owned();
+        report.checked += 1;
+        if let Err(err) = SettingsDBRow::try_from(item) {
+            report.corrupt.push(CorruptSettings { key, error: err.to_string() });
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,31 @@
+use fjall;
+
+use crate::errors::CustomError;
+use crate::models::{settings_format_version, SettingsDB, SettingsDBRow, SETTINGS_FORMAT_VERSION};
+
+/// Rewrites the settings stored in an older format in the current one, returns how many were migrated.
+/// Reads migrate the old rows anyway, the pass only saves doing it on every read.
+pub fn db_migrate_settings(db: &SettingsDB) -> Result<usize, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    let mut outdated = Vec::new();
+    for item in write_tx.iter(&db.partition) {
+        let (key, value) = item?;
+        if settings_format_version(&value).is_ok_and(|version| version >= SETTINGS_FORMAT_VERSION) {
+            continue;
+        }
+        // corrupt settings are left as they are, to be found by the integrity check
+        match SettingsDBRow::try_from((key.clone(), value)) {
+            Ok(settings) => outdated.push((key, settings)),
+            Err(err) => log::error!("{}", err),
+        }
+    }
+
+    for (key, settings) in &outdated {
+        write_tx.insert(&db.partition, &**key, settings.encode());
+    }
+    write_tx.commit()?;
+    Ok(outdated.len())
+}
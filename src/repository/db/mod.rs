@@ -1,7 +1,56 @@
+mod backup;
+mod change_requests;
+mod expirations;
+mod integrity;
+mod migrations;
+mod moves;
+mod schedules;
+mod transactions;
+mod trash;
 mod settings;
 
 pub use settings::db_create_settings;
+pub use settings::db_upsert_settings;
 pub use settings::db_get_settings;
+pub use settings::db_get_settings_list;
+pub use settings::db_get_settings_page;
 pub use settings::db_get_settings_by_key;
+pub use settings::db_get_settings_by_keys;
+pub use settings::db_search_settings;
 pub use settings::db_delete_settings_by_keys;
+pub use settings::db_delete_settings_by_prefix;
 pub use settings::db_update_settings_by_key;
+pub use settings::db_patch_settings;
+pub use settings::db_increment_settings;
+pub use settings::db_check_settings_update;
+pub use settings::db_set_settings_locked;
+pub use settings::db_update_settings_metadata;
+pub use settings::db_expire_settings;
+
+pub use schedules::db_create_scheduled_change;
+pub use schedules::db_get_scheduled_changes;
+pub use schedules::db_delete_scheduled_change;
+pub use schedules::db_apply_due_scheduled_changes;
+
+pub use change_requests::db_create_change_request;
+pub use change_requests::db_get_change_requests;
+pub use change_requests::db_approve_change_request;
+pub use change_requests::db_reject_change_request;
+
+pub use transactions::db_run_settings_transaction;
+
+pub use moves::db_rename_settings;
+pub use moves::db_copy_settings;
+
+pub use trash::db_get_trash;
+pub use trash::db_restore_settings;
+pub use trash::db_purge_trash;
+
+pub use migrations::db_migrate_settings;
+
+pub use expirations::db_index_expirations;
+
+pub use integrity::db_check_integrity;
+
+pub use backup::db_write_backup;
+pub use backup::db_restore_backup;
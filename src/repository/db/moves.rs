@@ -0,0 +1,107 @@
+use fjall;
+use chrono::Utc;
+
+use crate::errors::CustomError;
+use crate::models::{
+    MoveSettingsRequest, MovedSettings, ScheduledChangeDBRow, SettingsDB, SettingsDBRow
+};
+use super::expirations::index_expiration;
+use super::settings::locked_error;
+
+/// Moves the settings to the destination keys in one write transaction,
+/// keeping the value, metadata, version and timestamps. Pending scheduled changes follow the settings.
+pub fn db_rename_settings(
+    db: &SettingsDB,
+    request: &MoveSettingsRequest,
+) -> Result<Vec<MovedSettings>, CustomError> {
+    move_settings(db, request, false)
+}
+
+/// Copies the settings to the destination keys in one write transaction,
+/// the copies keep the value and metadata and start with a new version and timestamps.
+pub fn db_copy_settings(
+    db: &SettingsDB,
+    request: &MoveSettingsRequest,
+) -> Result<Vec<MovedSettings>, CustomError> {
+    move_settings(db, request, true)
+}
+
+fn move_settings(
+    db: &SettingsDB,
+    request: &MoveSettingsRequest,
+    keep_source: bool,
+) -> Result<Vec<MovedSettings>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    let sources: Vec<SettingsDBRow> = match request.prefix {
+        true => write_tx
+            .prefix(&db.partition, &request.from)
+            .map(|item| SettingsDBRow::try_from(item?))
+            .collect::<Result<Vec<SettingsDBRow>, CustomError>>()?,
+        false => write_tx
+            .get(&db.partition, &request.from)?
+            .map(|item| SettingsDBRow::try_from((request.from.as_str().into(), item)))
+            .into_iter()
+            .collect::<Result<Vec<SettingsDBRow>, CustomError>>()?,
+    };
+    if sources.is_empty() {
+        return Err(CustomError::NotFoundError(
+            format!("Settings with key '{}' not found", request.from)
+        ));
+    }
+
+    let now = Utc::now();
+    let mut moved = Vec::with_capacity(sources.len());
+    for settings in sources {
+        let destination = request.destination(&settings.key);
+        if write_tx.contains_key(&db.partition, &destination)? {
+            return Err(CustomError::ConflictError(
+                format!("Settings with key '{}' already exist", destination)
+            ));
+        }
+
+        let source = settings.key.clone();
+        let settings_row = match keep_source {
+            true => SettingsDBRow {
+                key: destination.clone(),
+                created_at: now,
+                updated_at: now,
+                version: 1,
+                ..settings
+            },
+            false => {
+                if settings.locked {
+                    return Err(locked_error(&source));
+                }
+                write_tx.remove(&db.partition, source.as_str());
+                SettingsDBRow { key: destination.clone(), ..settings }
+            }
+        };
+        let serialized: Vec<u8> = (&settings_row).into();
+        write_tx.insert(&db.partition, &destination, serialized);
+        index_expiration(db, &mut write_tx, &settings_row);
+        moved.push(MovedSettings { from: source, to: destination });
+    }
+
+    if !keep_source {
+        let mut scheduled_changes = Vec::new();
+        for item in write_tx.iter(&db.schedules) {
+            match ScheduledChangeDBRow::try_from(item?) {
+                Ok(scheduled_change) => scheduled_changes.push(scheduled_change),
+                Err(err) => log::error!("{}", err),
+            }
+        }
+        for scheduled_change in scheduled_changes {
+            if let Some(settings) = moved.iter().find(|settings| settings.from == scheduled_change.key) {
+                let scheduled_change = ScheduledChangeDBRow { key: settings.to.clone(), ..scheduled_change };
+                let serialized: Vec<u8> = (&scheduled_change).into();
+                write_tx.insert(&db.schedules, &scheduled_change.id, serialized);
+            }
+        }
+    }
+
+    write_tx.commit()?;
+    Ok(moved)
+}
@@ -0,0 +1,165 @@
+use fjall;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::enums::SettingsValueType;
+use crate::errors::CustomError;
+use crate::models::{ScheduledChangeDBRow, SettingsDB, UpdateSettingsRequest};
+use crate::repository::db_get_settings_by_key;
+use crate::utils::validate_settings_value;
+use super::settings::{seal_settings_value, update_settings_in_tx};
+
+pub fn db_create_scheduled_change(
+    db: &SettingsDB,
+    key: &str,
+    value: &str,
+    apply_at: DateTime<Utc>,
+) -> Result<Option<ScheduledChangeDBRow>, CustomError> {
+    let Some(settings) = db_get_settings_by_key(db, key)? else {
+        return Ok(None);
+    };
+    // validated against the current type now, and once again when applied
+    validate_settings_value(value.to_string(), settings.value_type.clone())?;
+
+    let secret = settings.value_type == SettingsValueType::Secret;
+    let scheduled_change = ScheduledChangeDBRow {
+        id: Uuid::new_v4().to_string(),
+        key: settings.key,
+        value: seal_settings_value(db, value.to_string(), &settings.value_type)?,
+        secret,
+        apply_at,
+        created_at: Utc::now(),
+    };
+
+    let serialized: Vec<u8> = (&scheduled_change).into();
+    db.schedules.insert(&scheduled_change.id, serialized)?;
+    db.keyspace.persist(fjall::PersistMode::SyncAll)?;
+
+    Ok(Some(scheduled_change))
+}
+
+/// Corrupt scheduled changes are logged and skipped, so that they do not stop the others
+pub fn db_get_scheduled_changes(
+    db: &SettingsDB,
+    key: Option<&str>,
+) -> Result<Vec<ScheduledChangeDBRow>, CustomError> {
+    let read_tx = db.keyspace.read_tx();
+    let mut scheduled_changes = Vec::new();
+    for item in read_tx.iter(&db.schedules) {
+        match ScheduledChangeDBRow::try_from(item?) {
+            Ok(scheduled_change) if key.is_none_or(|key| scheduled_change.key == key) => {
+                scheduled_changes.push(scheduled_change);
+            }
+            Ok(_) => {}
+            Err(err) => log::error!("{}", err),
+        }
+    }
+
+    scheduled_changes.sort_by_key(|scheduled_change| scheduled_change.apply_at);
+    Ok(scheduled_changes)
+}
+
+pub fn db_delete_scheduled_change(
+    db: &SettingsDB,
+    key: &str,
+    id: &str,
+) -> Result<Option<String>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    let Some(item) = write_tx.get(&db.schedules, id)? else {
+        return Ok(None);
+    };
+    let scheduled_change = ScheduledChangeDBRow::try_from((id.into(), item))?;
+    if scheduled_change.key != key {
+        return Ok(None);
+    }
+
+    write_tx.remove(&db.schedules, id);
+    write_tx.commit()?;
+    Ok(Some(scheduled_change.id))
+}
+
+/// Applies every scheduled change with `apply_at <= now` through the same validation
+/// as a regular update. Changes that can not be applied anymore are dropped,
+/// as well as the changes of protected settings, which need an approval.
+pub fn db_apply_due_scheduled_changes(
+    db: &SettingsDB,
+    now: DateTime<Utc>,
+    is_protected: impl Fn(&str) -> bool,
+) -> Result<usize, CustomError> {
+    let due_changes: Vec<ScheduledChangeDBRow> = db_get_scheduled_changes(db, None)?
+        .into_iter()
+        .filter(|scheduled_change| scheduled_change.apply_at <= now)
+        .collect();
+
+    let mut applied = 0;
+    for scheduled_change in due_changes {
+        let mut write_tx = db.keyspace.write_tx().durability(
+            Some(fjall::PersistMode::SyncAll)
+        );
+        // could be already applied or cancelled in the meantime
+        if !write_tx.contains_key(&db.schedules, &scheduled_change.id)? {
+            continue;
+        }
+        if is_protected(&scheduled_change.key) {
+            log::warn!(
+                "Scheduled change '{}' dropped: settings with key '{}' is protected",
+                scheduled_change.id, scheduled_change.key
+            );
+            write_tx.remove(&db.schedules, &scheduled_change.id);
+            write_tx.commit()?;
+            continue;
+        }
+
+        let value = match scheduled_change.secret {
+            true => db.cipher.decrypt(&scheduled_change.value),
+            false => Ok(scheduled_change.value.clone()),
+        };
+        let value = match value {
+            Ok(value) => value,
+            Err(err) => {
+                log::error!(
+                    "Scheduled change '{}' dropped: value for settings with key '{}' can not be decrypted: {}",
+                    scheduled_change.id, scheduled_change.key, err
+                );
+                write_tx.remove(&db.schedules, &scheduled_change.id);
+                write_tx.commit()?;
+                continue;
+            }
+        };
+        let payload = UpdateSettingsRequest {
+            key: scheduled_change.key.clone(),
+            value: Some(value),
+            ..Default::default()
+        };
+
+        match update_settings_in_tx(db, &mut write_tx, &payload) {
+            Ok(Some(_)) => {
+                log::info!(
+                    "Scheduled change '{}' applied to settings with key '{}'",
+                    scheduled_change.id, scheduled_change.key
+                );
+                applied += 1;
+            }
+            Ok(None) => log::warn!(
+                "Scheduled change '{}' dropped: settings with key '{}' not found",
+                scheduled_change.id, scheduled_change.key
+            ),
+            Err(err) => {
+                write_tx.rollback();
+                log::error!(
+                    "Scheduled change '{}' dropped: can not be applied to settings with key '{}': {}",
+                    scheduled_change.id, scheduled_change.key, err
+                );
+                db.schedules.remove(&scheduled_change.id)?;
+                continue;
+            }
+        }
+
+        write_tx.remove(&db.schedules, &scheduled_change.id);
+        write_tx.commit()?;
+    }
+    Ok(applied)
+}
@@ -1,92 +1,696 @@
-use fjall;
-use chrono::Utc;
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use fjall::{self, UserKey, UserValue, WriteTransaction};
+use chrono::{DateTime, Utc};
+use crate::enums::{DeleteOutcome, ExpireAction, SettingsSort, SettingsValueType};
 use crate::errors::CustomError;
-use crate::models::{SettingsDBRow, SettingsDB};
-use crate::utils::validate_settings_value;
+use crate::models::{
+    normalize_tags, ChangeRequestDBRow, CreateSettingsRequest, DeleteSettingsResult, IncrementSettingsRequest, GetSettingsByKeysResponse, GetSettingsQueryParams, SearchSettingsResponse, SettingsCursor, SettingsDBRow, SettingsDB, SettingsPageResponse,
+    SettingsPatch, SettingsRevert, UpdateSettingsMetadataRequest, UpdateSettingsRequest, UpdateSettingsDryRunResponse
+};
+use super::change_requests::create_change_request_in_tx;
+use super::expirations::{due_expirations, index_expiration, is_current_expiration};
+use super::trash::trash_settings_in_tx;
+use crate::utils::{validate_settings_value, SearchRank, SettingsSearch};
 
+/// The existence check and the insert are done in one serializable write transaction,
+/// so of concurrent creates of the same key exactly one succeeds.
 pub fn db_create_settings(
     db: &SettingsDB,
     settings_row: &SettingsDBRow,
-) -> Result<Option<String>, fjall::Error> {
-    let key = &settings_row.key;
+) -> Result<Option<String>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
 
-    let Some(_) = db.partition.get(key)? else {
-        let serialized: Vec<u8> = settings_row.into();
-        db.partition
-            .insert(key, serialized)
-            .expect("Failed to insert settings");
-        db.keyspace.persist(fjall::PersistMode::SyncAll)?;
+    let Some(settings_row) = create_settings_in_tx(db, &mut write_tx, settings_row)? else {
+        return Ok(None);
+    };
+    write_tx.commit()?;
+    Ok(Some(settings_row.key))
+}
+
+/// Creates the settings or updates the value, type and expiry of the existing one in one transaction,
+/// the metadata and `created_at` of the existing settings are preserved.
+/// Returns the settings and whether it was created.
+pub fn db_upsert_settings(
+    db: &SettingsDB,
+    request: &CreateSettingsRequest,
+) -> Result<(SettingsDBRow, bool), CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
 
-        return Ok(Some(key.clone()));
+    let result = match update_settings_in_tx(db, &mut write_tx, &request.as_update())? {
+        Some(settings_row) => (settings_row, false),
+        None => {
+            let settings_row = SettingsDBRow::from(request.clone());
+            let settings_row = create_settings_in_tx(db, &mut write_tx, &settings_row)?
+                .expect("Settings should not exist in the same transaction");
+            (settings_row, true)
+        }
     };
-    Ok(None)
+    write_tx.commit()?;
+    Ok(result)
 }
 
 pub fn db_get_settings_by_key(
     db: &SettingsDB,
     key: &str,
-) -> Result<Option<SettingsDBRow>, fjall::Error> {
+) -> Result<Option<SettingsDBRow>, CustomError> {
     let Some(item) = db.partition.get(key)? else {
         return Ok(None);
     };
 
-    let settings: SettingsDBRow = rmp_serde::from_slice(&item)
-        .expect("Error deserializing settings from bytes");
+    let settings = SettingsDBRow::try_from((key.into(), item))?;
     Ok(Some(settings))
 }
 
+/// All keys are read from the same snapshot, so the values are consistent with each other
+pub fn db_get_settings_by_keys(
+    db: &SettingsDB,
+    keys: &[String],
+) -> Result<GetSettingsByKeysResponse, CustomError> {
+    let read_tx = db.keyspace.read_tx();
+
+    let mut response = GetSettingsByKeysResponse {
+        found: Vec::new(),
+        missing: Vec::new(),
+        corrupt_keys: Vec::new(),
+    };
+    for (i, key) in keys.iter().enumerate() {
+        if keys[..i].contains(key) {
+            continue;
+        }
+        match read_tx.get(&db.partition, key)? {
+            Some(item) => {
+                if let Some(settings) = skip_corrupt((key.as_str().into(), item), &mut response.corrupt_keys) {
+                    response.found.push(settings);
+                }
+            }
+            None => response.missing.push(key.clone()),
+        }
+    }
+    Ok(response)
+}
+
+/// Returns the settings under the prefix and the keys of the corrupt ones
 pub fn db_get_settings(
     db: &SettingsDB,
     prefix: String,
-) -> Result<Vec<SettingsDBRow>, fjall::Error> {
+) -> Result<(Vec<SettingsDBRow>, Vec<String>), CustomError> {
     let read_tx = db.keyspace.read_tx();
-    let settings = read_tx
-        .prefix(&db.partition, prefix)
-        .map(|item| item.map(SettingsDBRow::from))
-        .collect::<Result<Vec<SettingsDBRow>, _>>()?;
+    let mut corrupt_keys = Vec::new();
+    let mut settings = Vec::new();
+    for item in read_tx.prefix(&db.partition, prefix) {
+        settings.extend(skip_corrupt(item?, &mut corrupt_keys));
+    }
+
+    Ok((settings, corrupt_keys))
+}
+
+/// Lists skip the corrupt settings and collect their keys, so that one bad row does not fail the whole list
+fn skip_corrupt(item: (UserKey, UserValue), corrupt_keys: &mut Vec<String>) -> Option<SettingsDBRow> {
+    let key = String::from_utf8_lossy(&item.0).into_owned();
+    match SettingsDBRow::try_from(item) {
+        Ok(settings) => Some(settings),
+        Err(err) => {
+            log::error!("{}", err);
+            corrupt_keys.push(key);
+            None
+        }
+    }
+}
+
+/// The whole list without pagination and the keys of the corrupt settings
+pub fn db_get_settings_list(
+    db: &SettingsDB,
+    query: &GetSettingsQueryParams,
+) -> Result<(Vec<SettingsDBRow>, Vec<String>), CustomError> {
+    let (mut settings, corrupt_keys) = db_get_settings(db, query.prefix.clone())?;
+    settings.retain(|settings| query.matches(settings));
+    if query.sort == SettingsSort::UpdatedAt {
+        settings.sort_by(compare_by_updated_at);
+    }
+    Ok((settings, corrupt_keys))
+}
 
-    Ok(settings)
+/// Sorted by key the page is read with a range scan starting right after the cursor,
+/// sorted by `updated_at` the whole prefix has to be read and sorted.
+pub fn db_get_settings_page(
+    db: &SettingsDB,
+    query: &GetSettingsQueryParams,
+) -> Result<SettingsPageResponse, CustomError> {
+    let cursor = query.cursor.as_deref().map(SettingsCursor::decode).transpose()?;
+    if let Some(cursor) = &cursor {
+        cursor.validate(&query.sort)?;
+    }
+    let limit = query.page_limit();
+    let read_tx = db.keyspace.read_tx();
+
+    // one more than the limit, to know if there is a next page
+    let mut items: Vec<SettingsDBRow> = Vec::with_capacity(limit + 1);
+    let mut corrupt_keys = Vec::new();
+    match query.sort {
+        SettingsSort::Key => {
+            let start = match cursor {
+                Some(cursor) if cursor.key >= query.prefix => Bound::Excluded(cursor.key),
+                _ => Bound::Included(query.prefix.clone()),
+            };
+            for item in read_tx.range(&db.partition, (start, Bound::Unbounded)) {
+                let item = item?;
+                if !item.0.starts_with(query.prefix.as_bytes()) {
+                    break;
+                }
+                let Some(settings) = skip_corrupt(item, &mut corrupt_keys) else {
+                    continue;
+                };
+                if !query.matches(&settings) {
+                    continue;
+                }
+                items.push(settings);
+                if items.len() > limit {
+                    break;
+                }
+            }
+        }
+        SettingsSort::UpdatedAt => {
+            let mut settings = Vec::new();
+            for item in read_tx.prefix(&db.partition, &query.prefix) {
+                settings.extend(skip_corrupt(item?, &mut corrupt_keys).filter(|settings| query.matches(settings)));
+            }
+            settings.sort_by(compare_by_updated_at);
+
+            let after_cursor = |settings: &SettingsDBRow| match &cursor {
+                Some(SettingsCursor { key, updated_at: Some(updated_at), .. }) => {
+                    settings.updated_at < *updated_at
+                        || (settings.updated_at == *updated_at && settings.key > *key)
+                }
+                Some(SettingsCursor { updated_at: None, .. }) | None => true,
+            };
+            items.extend(
+                settings.into_iter().filter(after_cursor).take(limit + 1)
+            );
+        }
+    }
+
+    let next_cursor = match items.len() > limit {
+        true => {
+            items.truncate(limit);
+            items.last().map(|last| SettingsCursor {
+                key: last.key.clone(),
+                updated_at: (query.sort == SettingsSort::UpdatedAt).then_some(last.updated_at),
+                sort: query.sort.clone(),
+            }.encode())
+        }
+        false => None,
+    };
+    Ok(SettingsPageResponse { items, next_cursor, corrupt_keys })
 }
 
+/// Most recently updated first, the key breaks the ties so that the order is stable
+fn compare_by_updated_at(a: &SettingsDBRow, b: &SettingsDBRow) -> Ordering {
+    b.updated_at.cmp(&a.updated_at).then_with(|| a.key.cmp(&b.key))
+}
+
+/// Scans the partition in key order keeping every exact key match, at most `limit` other key matches
+/// and `limit` other matches. Once there are enough key matches only the keys are compared,
+/// as an exact match in another casing, ranked before them, can still come later in the scan.
+pub fn db_search_settings(
+    db: &SettingsDB,
+    search: &SettingsSearch,
+    value_type: Option<&SettingsValueType>,
+    limit: usize,
+) -> Result<SearchSettingsResponse, CustomError> {
+    let mut exact_matches: Vec<SettingsDBRow> = Vec::new();
+    let mut key_matches: Vec<SettingsDBRow> = Vec::new();
+    let mut other_matches: Vec<SettingsDBRow> = Vec::new();
+    let mut corrupt_keys = Vec::new();
+
+    for item in db.keyspace.read_tx().iter(&db.partition) {
+        let (key, value) = item?;
+        if key_matches.len() >= limit && !search.is_exact(&String::from_utf8_lossy(&key)) {
+            continue;
+        }
+        let Some(settings) = skip_corrupt((key, value), &mut corrupt_keys) else {
+            continue;
+        };
+        if value_type.is_some_and(|value_type| settings.value_type != *value_type) {
+            continue;
+        }
+
+        match search.rank(&settings) {
+            Some(SearchRank::ExactKey) => exact_matches.push(settings),
+            Some(SearchRank::Key) if key_matches.len() < limit => key_matches.push(settings),
+            Some(SearchRank::Other) if other_matches.len() < limit => other_matches.push(settings),
+            _ => {}
+        }
+    }
+
+    let items = exact_matches
+        .into_iter()
+        .chain(key_matches)
+        .chain(other_matches)
+        .take(limit)
+        .collect();
+    Ok(SearchSettingsResponse { items, corrupt_keys })
+}
+
+/// Moves the settings to the trash and creates delete change requests for the protected ones in one transaction,
+/// returns the outcome for every key and the change requests. Locked settings are skipped.
+/// Fails when every given settings is locked, so that nothing would be deleted,
+/// and in `strict` mode when any of the keys is not found.
 pub fn db_delete_settings_by_keys(
     db: &SettingsDB,
     keys: Vec<String>,
-) -> Result<(), fjall::Error> {
+    is_protected: impl Fn(&str) -> bool,
+    requested_by: &str,
+    strict: bool,
+) -> Result<(Vec<DeleteSettingsResult>, Vec<ChangeRequestDBRow>), CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    let mut results: Vec<DeleteSettingsResult> = Vec::with_capacity(keys.len());
+    let mut change_requests = Vec::new();
     for key in keys {
-        db.partition.remove(&key)?;
+        if results.iter().any(|result| result.key == key) {
+            continue;
+        }
+        let outcome = match write_tx.get(&db.partition, &key)? {
+            None => DeleteOutcome::NotFound,
+            Some(item) => match SettingsDBRow::try_from((key.as_str().into(), item)) {
+                Ok(settings) if settings.locked => DeleteOutcome::Locked,
+                // deletes of protected settings go live only after an approval
+                Ok(settings) if is_protected(&key) => {
+                    change_requests.push(
+                        create_change_request_in_tx(db, &mut write_tx, &settings, None, requested_by)?
+                    );
+                    DeleteOutcome::PendingApproval
+                }
+                Ok(settings) => {
+                    trash_settings_in_tx(db, &mut write_tx, settings);
+                    DeleteOutcome::Deleted
+                }
+                // can not be restored, so it is not moved to the trash
+                Err(err) => {
+                    log::warn!("{}, deleted without moving to the trash", err);
+                    write_tx.remove(&db.partition, key.as_str());
+                    DeleteOutcome::Deleted
+                }
+            },
+        };
+        results.push(DeleteSettingsResult { key, outcome });
+    }
+
+    let keys_with = |outcome: DeleteOutcome| results
+        .iter()
+        .filter(|result| result.outcome == outcome)
+        .map(|result| result.key.as_str())
+        .collect::<Vec<&str>>();
+
+    let not_found = keys_with(DeleteOutcome::NotFound);
+    if strict && !not_found.is_empty() {
+        return Err(CustomError::NotFoundError(
+            format!("Settings with keys '{}' not found", not_found.join("', '"))
+        ));
+    }
+
+    let locked = keys_with(DeleteOutcome::Locked);
+    if !results.is_empty() && locked.len() == results.len() {
+        return match locked.as_slice() {
+            [key] => Err(locked_error(key)),
+            _ => Err(CustomError::ForbiddenError(
+                format!("Settings with keys '{}' are locked", locked.join("', '"))
+            )),
+        };
     }
-    db.keyspace.persist(fjall::PersistMode::SyncAll)?;
-    Ok(())
+
+    write_tx.commit()?;
+    Ok((results, change_requests))
+}
+
+/// Moves all the settings under the prefix to the trash in one transaction.
+/// Nothing is deleted when the number of settings differs from `confirm` or any of them is locked.
+pub fn db_delete_settings_by_prefix(
+    db: &SettingsDB,
+    prefix: &str,
+    confirm: usize,
+) -> Result<Vec<String>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    let settings = write_tx
+        .prefix(&db.partition, prefix)
+        .map(|item| SettingsDBRow::try_from(item?))
+        .collect::<Result<Vec<SettingsDBRow>, CustomError>>()?;
+
+    if settings.len() != confirm {
+        return Err(CustomError::ConflictError(format!(
+            "Expected {} settings with prefix '{}', found {}",
+            confirm, prefix, settings.len()
+        )));
+    }
+    if let Some(locked) = settings.iter().find(|settings| settings.locked) {
+        return Err(locked_error(&locked.key));
+    }
+
+    let keys = settings.iter().map(|settings| settings.key.clone()).collect();
+    for settings in settings {
+        trash_settings_in_tx(db, &mut write_tx, settings);
+    }
+    write_tx.commit()?;
+    Ok(keys)
 }
 
 pub fn db_update_settings_by_key(
     db: &SettingsDB,
-    key: &String,
-    value: &String,
+    payload: &UpdateSettingsRequest,
 ) -> Result<Option<String>, CustomError> {
     let mut write_tx = db.keyspace.write_tx().durability(
         Some(fjall::PersistMode::SyncAll)
     );
 
+    let settings = update_settings_in_tx(db, &mut write_tx, payload)?;
+    if settings.is_some() {
+        write_tx.commit()?;
+    }
+    Ok(settings.map(|settings| settings.key))
+}
+
+/// Applies the patch to the current value inside the update transaction,
+/// so that concurrent patches of different fields do not overwrite each other.
+pub fn db_patch_settings(
+    db: &SettingsDB,
+    key: &str,
+    patch: &SettingsPatch,
+) -> Result<Option<SettingsDBRow>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
     let Some(item) = write_tx.get(&db.partition, key)? else {
         return Ok(None);
     };
-    let settings: SettingsDBRow = rmp_serde::from_slice(&item)
-        .expect("Error deserializing settings from bytes");
+    let settings = SettingsDBRow::try_from((key.into(), item))?;
 
-    validate_settings_value(value.clone(), settings.value_type.clone())?;
+    let payload = value_update(&settings, patch.apply(&settings)?);
+    let settings_row = update_settings_in_tx(db, &mut write_tx, &payload)?;
+    write_tx.commit()?;
+    Ok(settings_row)
+}
 
-    let settings_row = &SettingsDBRow {
+/// Reads, increments and writes the value in one transaction, so that concurrent increments are not lost
+pub fn db_increment_settings(
+    db: &SettingsDB,
+    key: &str,
+    increment: &IncrementSettingsRequest,
+) -> Result<Option<SettingsDBRow>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+    let Some(item) = write_tx.get(&db.partition, key)? else {
+        return Ok(None);
+    };
+    let settings = SettingsDBRow::try_from((key.into(), item))?;
+
+    let payload = value_update(&settings, increment.apply(&settings)?);
+    let settings_row = update_settings_in_tx(db, &mut write_tx, &payload)?;
+    write_tx.commit()?;
+    Ok(settings_row)
+}
+
+/// Update of the value only, a temporary override stays one and expires as before
+fn value_update(settings: &SettingsDBRow, value: String) -> UpdateSettingsRequest {
+    UpdateSettingsRequest {
         key: settings.key.clone(),
-        value: value.to_string(),
-        value_type: settings.value_type,
-        created_at: settings.created_at,
+        value: Some(value),
+        expires_at: settings.expires_at,
+        on_expire: Some(match settings.revert_to {
+            Some(_) => ExpireAction::Revert,
+            None => ExpireAction::Delete,
+        }),
+        ..Default::default()
+    }
+}
+
+/// Writes the new settings into the given transaction without committing it,
+/// `None` if the settings already exist.
+pub(crate) fn create_settings_in_tx(
+    db: &SettingsDB,
+    write_tx: &mut WriteTransaction,
+    settings_row: &SettingsDBRow,
+) -> Result<Option<SettingsDBRow>, CustomError> {
+    if write_tx.contains_key(&db.partition, &settings_row.key)? {
+        return Ok(None);
+    }
+
+    let settings_row = SettingsDBRow {
+        value: seal_settings_value(db, settings_row.value.clone(), &settings_row.value_type)?,
+        ..settings_row.clone()
+    };
+    let serialized: Vec<u8> = (&settings_row).into();
+
+    write_tx.insert(&db.partition, &settings_row.key, serialized);
+    index_expiration(db, write_tx, &settings_row);
+    Ok(Some(settings_row))
+}
+
+/// Validates and writes the update into the given transaction without committing it,
+/// so that it can be combined with other writes.
+pub(crate) fn update_settings_in_tx(
+    db: &SettingsDB,
+    write_tx: &mut WriteTransaction,
+    payload: &UpdateSettingsRequest,
+) -> Result<Option<SettingsDBRow>, CustomError> {
+    let Some(item) = write_tx.get(&db.partition, &payload.key)? else {
+        return Ok(None);
+    };
+    let settings = SettingsDBRow::try_from((payload.key.as_str().into(), item))?;
+    if settings.locked {
+        return Err(locked_error(&settings.key));
+    }
+
+    let value_type = payload.value_type.clone().unwrap_or(settings.value_type.clone());
+    let value = validate_settings_update(db, &settings, payload.value.as_ref(), &value_type)?;
+
+    // an override on top of an override still reverts to the original value
+    let expires_at = payload.expires_at();
+    let revert_to = match (expires_at, payload.on_expire.as_ref()) {
+        (Some(_), None | Some(ExpireAction::Revert)) => settings.revert_to.clone().or(
+            Some(SettingsRevert {
+                value: settings.value.clone(),
+                value_type: settings.value_type.clone(),
+            })
+        ),
+        _ => None,
+    };
+
+    let settings_row = SettingsDBRow {
+        key: settings.key.clone(),
+        value: seal_settings_value(db, value, &value_type)?,
+        value_type,
+        updated_at: Utc::now(),
+        expires_at,
+        revert_to,
+        version: settings.version + 1,
+        ..settings
+    };
+    let serialized: Vec<u8> = (&settings_row).into();
+
+    write_tx.insert(&db.partition, &payload.key, serialized);
+    index_expiration(db, write_tx, &settings_row);
+    Ok(Some(settings_row))
+}
+
+/// Reverts or deletes every settings with `expires_at <= now`, found with a range scan of the `expirations` index
+pub fn db_expire_settings(
+    db: &SettingsDB,
+    now: DateTime<Utc>,
+) -> Result<usize, CustomError> {
+    let mut expired = 0;
+    for (index_key, key) in due_expirations(db, now)? {
+        let mut write_tx = db.keyspace.write_tx().durability(
+            Some(fjall::PersistMode::SyncAll)
+        );
+        let settings = match write_tx.get(&db.partition, &key)? {
+            Some(item) => match SettingsDBRow::try_from((key.as_str().into(), item)) {
+                Ok(settings) => Some(settings),
+                Err(err) => {
+                    log::error!("{}", err);
+                    None
+                }
+            },
+            None => None,
+        };
+        // deleted, corrupt or updated with another expiry since the entry was added
+        let Some(settings) = settings.filter(|settings| is_current_expiration(&index_key, settings)) else {
+            write_tx.remove(&db.expirations, index_key);
+            write_tx.commit()?;
+            continue;
+        };
+        // locked settings keep their entry and expire once unlocked
+        if !settings.is_expired(now) || settings.locked {
+            continue;
+        }
+
+        match settings.revert_to.clone() {
+            Some(revert_to) => {
+                let settings_row = &SettingsDBRow {
+                    value: revert_to.value,
+                    value_type: revert_to.value_type,
+                    updated_at: Utc::now(),
+                    expires_at: None,
+                    revert_to: None,
+                    version: settings.version + 1,
+                    ..settings
+                };
+                let serialized: Vec<u8> = settings_row.into();
+                write_tx.insert(&db.partition, &key, serialized);
+                log::info!("Settings with key '{}' expired and reverted", key);
+            }
+            None => {
+                write_tx.remove(&db.partition, key.as_str());
+                log::info!("Settings with key '{}' expired and deleted", key);
+            }
+        }
+        write_tx.remove(&db.expirations, index_key);
+        write_tx.commit()?;
+        expired += 1;
+    }
+    Ok(expired)
+}
+
+pub fn db_set_settings_locked(
+    db: &SettingsDB,
+    key: &str,
+    locked: bool,
+) -> Result<Option<SettingsDBRow>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+    let Some(item) = write_tx.get(&db.partition, key)? else {
+        return Ok(None);
+    };
+    let settings = SettingsDBRow::try_from((key.into(), item))?;
+
+    let settings_row = SettingsDBRow {
+        locked,
+        ..settings
+    };
+    let serialized: Vec<u8> = (&settings_row).into();
+
+    write_tx.insert(&db.partition, key, serialized);
+    write_tx.commit()?;
+    Ok(Some(settings_row))
+}
+
+pub fn db_update_settings_metadata(
+    db: &SettingsDB,
+    key: &str,
+    payload: &UpdateSettingsMetadataRequest,
+) -> Result<Option<SettingsDBRow>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+    let Some(item) = write_tx.get(&db.partition, key)? else {
+        return Ok(None);
+    };
+    let settings = SettingsDBRow::try_from((key.into(), item))?;
+
+    let settings_row = SettingsDBRow {
+        description: match payload.description.as_ref() {
+            Some(description) => Some(description.clone()).filter(|d| !d.is_empty()),
+            None => settings.description.clone(),
+        },
+        owner: match payload.owner.as_ref() {
+            Some(owner) => Some(owner.clone()).filter(|o| !o.is_empty()),
+            None => settings.owner.clone(),
+        },
+        tags: match payload.tags.as_ref() {
+            Some(tags) => normalize_tags(tags.clone()),
+            None => settings.tags.clone(),
+        },
         updated_at: Utc::now(),
+        ..settings
     };
-    let serialized: Vec<u8> = settings_row.into();
+    let serialized: Vec<u8> = (&settings_row).into();
 
     write_tx.insert(&db.partition, key, serialized);
     write_tx.commit()?;
+    Ok(Some(settings_row))
+}
+
+pub fn db_check_settings_update(
+    db: &SettingsDB,
+    key: &str,
+    value: Option<&String>,
+    value_type: SettingsValueType,
+) -> Result<Option<UpdateSettingsDryRunResponse>, CustomError> {
+    let Some(settings) = db_get_settings_by_key(db, key)? else {
+        return Ok(None);
+    };
+
+    let result = validate_settings_update(db, &settings, value, &value_type);
+    Ok(Some(UpdateSettingsDryRunResponse {
+        key: settings.key,
+        from: settings.value_type,
+        to: value_type,
+        convertible: result.is_ok(),
+        message: result.err().map(|err| err.to_string()),
+    }))
+}
+
+/// Returns the plain value that would be stored after the update.
+/// When no new value is given the current one is checked against the new type.
+pub(crate) fn validate_settings_update(
+    db: &SettingsDB,
+    settings: &SettingsDBRow,
+    value: Option<&String>,
+    value_type: &SettingsValueType,
+) -> Result<String, CustomError> {
+    let Some(value) = value else {
+        let value = open_settings_value(db, settings.value.clone(), &settings.value_type)?;
+        return match validate_settings_value(value.clone(), value_type.clone()) {
+            Ok(()) => Ok(value),
+            // do not echo a decrypted secret back in the error message
+            Err(_) if settings.value_type == SettingsValueType::Secret => {
+                Err(CustomError::ValidationError(format!(
+                    "Secret value can not be converted to '{}'",
+                    value_type
+                )))
+            }
+            Err(err) => Err(err),
+        };
+    };
 
-    Ok(Some(settings.key))
+    validate_settings_value(value.clone(), value_type.clone())?;
+    Ok(value.clone())
+}
+
+pub(crate) fn locked_error(key: &str) -> CustomError {
+    CustomError::ForbiddenError(format!("Settings with key '{}' is locked", key))
+}
+
+pub(crate) fn seal_settings_value(
+    db: &SettingsDB,
+    value: String,
+    value_type: &SettingsValueType,
+) -> Result<String, CustomError> {
+    match value_type {
+        SettingsValueType::Secret => db.cipher.encrypt(&value),
+        _ => Ok(value),
+    }
+}
+
+fn open_settings_value(
+    db: &SettingsDB,
+    value: String,
+    value_type: &SettingsValueType,
+) -> Result<String, CustomError> {
+    match value_type {
+        SettingsValueType::Secret => db.cipher.decrypt(&value),
+        _ => Ok(value),
+    }
 }
@@ -0,0 +1,80 @@
+use fjall;
+
+use crate::errors::CustomError;
+use crate::models::{
+    SettingsDB, SettingsDBRow, SettingsOperation, SettingsOperationResult, SettingsTransactionRequest,
+    SettingsTransactionResponse
+};
+use super::settings::{create_settings_in_tx, locked_error, update_settings_in_tx};
+use super::trash::trash_settings_in_tx;
+
+/// Applies all the operations in one write transaction, the first failed operation
+/// or unexpected version fails the whole transaction and nothing is written.
+pub fn db_run_settings_transaction(
+    db: &SettingsDB,
+    request: &SettingsTransactionRequest,
+) -> Result<SettingsTransactionResponse, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    for (key, expected_version) in &request.expected_versions {
+        let version = write_tx.get(&db.partition, key)?
+            .map(|item| SettingsDBRow::try_from((key.as_str().into(), item)))
+            .transpose()?
+            .map(|settings| settings.version);
+        if version != Some(*expected_version) {
+            return Err(CustomError::ConflictError(match version {
+                Some(version) => format!(
+                    "Settings with key '{}' has version {}, expected {}", key, version, expected_version
+                ),
+                None => format!("Settings with key '{}' not found, expected version {}", key, expected_version),
+            }));
+        }
+    }
+
+    let mut results = Vec::with_capacity(request.operations.len());
+    for operation in &request.operations {
+        let key = operation.key();
+        let version = match operation {
+            SettingsOperation::Create(create) => {
+                let settings_row = SettingsDBRow::from(create.clone());
+                let Some(settings_row) = create_settings_in_tx(db, &mut write_tx, &settings_row)? else {
+                    return Err(CustomError::ConflictError(
+                        format!("Settings with key '{}' already exist", key)
+                    ));
+                };
+                Some(settings_row.version)
+            }
+            SettingsOperation::Update(update) => {
+                let Some(settings_row) = update_settings_in_tx(db, &mut write_tx, update)? else {
+                    return Err(not_found_error(key));
+                };
+                Some(settings_row.version)
+            }
+            SettingsOperation::Delete(_) => {
+                let Some(item) = write_tx.get(&db.partition, key)? else {
+                    return Err(not_found_error(key));
+                };
+                let settings = SettingsDBRow::try_from((key.into(), item))?;
+                if settings.locked {
+                    return Err(locked_error(key));
+                }
+                trash_settings_in_tx(db, &mut write_tx, settings);
+                None
+            }
+        };
+        results.push(SettingsOperationResult {
+            op: operation.name().to_string(),
+            key: key.to_string(),
+            version,
+        });
+    }
+
+    write_tx.commit()?;
+    Ok(SettingsTransactionResponse { results })
+}
+
+fn not_found_error(key: &str) -> CustomError {
+    CustomError::NotFoundError(format!("Settings with key '{}' not found", key))
+}
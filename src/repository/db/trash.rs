@@ -0,0 +1,112 @@
+use std::cmp::Reverse;
+
+use fjall::{self, WriteTransaction};
+use chrono::{DateTime, Utc};
+
+use crate::errors::CustomError;
+use crate::models::{SettingsDB, SettingsDBRow, TrashedSettingsDBRow};
+use super::expirations::index_expiration;
+
+/// Moves the settings to the trash within the given transaction without committing it.
+/// A settings deleted again replaces its previous copy in the trash.
+pub(crate) fn trash_settings_in_tx(
+    db: &SettingsDB,
+    write_tx: &mut WriteTransaction,
+    settings: SettingsDBRow,
+) {
+    let key = settings.key.clone();
+    let trashed = TrashedSettingsDBRow {
+        settings,
+        deleted_at: Utc::now(),
+    };
+    let serialized: Vec<u8> = (&trashed).into();
+
+    write_tx.remove(&db.partition, key.as_str());
+    write_tx.insert(&db.trash, &key, serialized);
+}
+
+/// Corrupt trashed settings are logged and skipped
+pub fn db_get_trash(
+    db: &SettingsDB,
+) -> Result<Vec<TrashedSettingsDBRow>, CustomError> {
+    let read_tx = db.keyspace.read_tx();
+    let mut trashed = Vec::new();
+    for item in read_tx.iter(&db.trash) {
+        match TrashedSettingsDBRow::try_from(item?) {
+            Ok(item) => trashed.push(item),
+            Err(err) => log::error!("{}", err),
+        }
+    }
+
+    // most recently deleted first
+    trashed.sort_by_key(|trashed| Reverse(trashed.deleted_at));
+    Ok(trashed)
+}
+
+/// Restores all the settings or none of them: fails if any of them is not in the trash
+/// or a settings with the same key was created in the meantime
+pub fn db_restore_settings(
+    db: &SettingsDB,
+    keys: &[String],
+) -> Result<Vec<String>, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    let mut restored = Vec::with_capacity(keys.len());
+    for key in keys {
+        if restored.contains(key) {
+            continue;
+        }
+        let Some(item) = write_tx.get(&db.trash, key)? else {
+            return Err(CustomError::NotFoundError(
+                format!("Settings with key '{}' not found in the trash", key)
+            ));
+        };
+        if write_tx.contains_key(&db.partition, key)? {
+            return Err(CustomError::ConflictError(
+                format!("Settings with key '{}' already exist", key)
+            ));
+        }
+
+        let trashed = TrashedSettingsDBRow::try_from((key.as_str().into(), item))?;
+        let serialized: Vec<u8> = (&trashed.settings).into();
+        write_tx.insert(&db.partition, key, serialized);
+        index_expiration(db, &mut write_tx, &trashed.settings);
+        write_tx.remove(&db.trash, key.as_str());
+        restored.push(key.clone());
+    }
+
+    write_tx.commit()?;
+    Ok(restored)
+}
+
+/// Permanently deletes the settings moved to the trash before `deleted_before`
+pub fn db_purge_trash(
+    db: &SettingsDB,
+    deleted_before: DateTime<Utc>,
+) -> Result<usize, CustomError> {
+    let mut write_tx = db.keyspace.write_tx().durability(
+        Some(fjall::PersistMode::SyncAll)
+    );
+
+    // corrupt trashed settings are left as they are, to be found by the integrity check
+    let mut purged_keys = Vec::new();
+    for item in write_tx.iter(&db.trash) {
+        match TrashedSettingsDBRow::try_from(item?) {
+            Ok(trashed) if trashed.deleted_at < deleted_before => purged_keys.push(trashed.settings.key),
+            Ok(_) => {}
+            Err(err) => log::error!("{}", err),
+        }
+    }
+
+    for key in &purged_keys {
+        write_tx.remove(&db.trash, key.as_str());
+    }
+    write_tx.commit()?;
+
+    if !purged_keys.is_empty() {
+        log::info!("{} settings purged from the trash", purged_keys.len());
+    }
+    Ok(purged_keys.len())
+}
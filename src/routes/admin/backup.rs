@@ -0,0 +1,67 @@
+use std::io::{self, Write};
+
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::web::{self, Bytes};
+use actix_web::HttpResponse;
+use chrono::Utc;
+use tokio::sync::mpsc;
+
+use crate::enums::ApiKeyScope;
+use crate::errors::CustomError;
+use crate::models::SettingsDB;
+use crate::repository::db_write_backup;
+
+/// Chunks buffered between the archive writer and the response
+const BACKUP_CHUNKS: usize = 16;
+
+/// Sends everything written to it as chunks of the response body
+struct ChunkWriter(mpsc::Sender<Result<Bytes, io::Error>>);
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "Backup download was closed"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub async fn download_backup(
+    db: web::Data<SettingsDB>,
+    scope: ApiKeyScope,
+) -> Result<HttpResponse, CustomError> {
+    scope.require_admin()?;
+
+    // the archive is written while it is sent, so the whole of it is never kept in memory
+    let (sender, receiver) = mpsc::channel(BACKUP_CHUNKS);
+    tokio::task::spawn_blocking(move || {
+        let errors = sender.clone();
+        match db_write_backup(&db, ChunkWriter(sender)) {
+            Ok(manifest) => log::info!(
+                target: "audit",
+                "Backup downloaded with admin api key, {} settings", manifest.partitions["settings"]
+            ),
+            Err(err) => {
+                log::error!("Backup failed: {}", err);
+                // aborts the response, so that a truncated archive is not taken for a complete one
+                let _ = errors.blocking_send(Err(io::Error::other(err.to_string())));
+            }
+        }
+    });
+    let body = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    });
+
+    let filename = format!("dynamic-settings-{}.tar.gz", Utc::now().format("%Y%m%dT%H%M%SZ"));
+    Ok(HttpResponse::Ok()
+        .content_type("application/gzip")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(filename)],
+        })
+        .streaming(body))
+}
@@ -0,0 +1,17 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::enums::ApiKeyScope;
+use crate::errors::CustomError;
+use crate::models::SettingsDB;
+use crate::repository::db_check_integrity;
+
+pub async fn check_integrity(
+    db: web::Data<SettingsDB>,
+    scope: ApiKeyScope,
+) -> Result<HttpResponse, CustomError> {
+    scope.require_admin()?;
+
+    let report = db_check_integrity(&db)?;
+    Ok(HttpResponse::Ok().json(report))
+}
@@ -0,0 +1,5 @@
+mod backup;
+mod integrity;
+
+pub use backup::download_backup;
+pub use integrity::check_integrity;
@@ -1,14 +1,15 @@
 use actix_web::web;
 use actix_web::HttpResponse;
+use crate::config::Config;
 use crate::errors::CustomError;
 use crate::models::{MessageResponse, ValidateTokenRequest};
 
 pub async fn validate_token(
-    api_key: web::Data<String>,
+    config: web::Data<Config>,
     payload: web::Json<ValidateTokenRequest>,
 ) -> Result<HttpResponse, CustomError> {
     let token = payload.into_inner().token;
-    if token != api_key.to_string() {
+    if token != config.api_key && token != config.admin_api_key {
         return Err(CustomError::ForbiddenError("Invalid token".to_string()));
     }
     Ok(HttpResponse::Ok().json(MessageResponse {
@@ -0,0 +1,17 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::errors::CustomError;
+use crate::models::{ChangeRequestDBRow, GetChangeRequestsQueryParams, SettingsDB};
+use crate::repository::db_get_change_requests;
+
+pub async fn get_change_requests(
+    db: web::Data<SettingsDB>,
+    query: web::Query<GetChangeRequestsQueryParams>,
+) -> Result<HttpResponse, CustomError> {
+    let change_requests: Vec<ChangeRequestDBRow> = db_get_change_requests(&db, query.status.as_ref())?
+        .into_iter()
+        .map(ChangeRequestDBRow::masked)
+        .collect();
+    Ok(HttpResponse::Ok().json(change_requests))
+}
@@ -0,0 +1,6 @@
+mod get;
+mod resolve;
+
+pub use get::get_change_requests;
+pub use resolve::approve_change_request;
+pub use resolve::reject_change_request;
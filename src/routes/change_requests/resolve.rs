@@ -0,0 +1,28 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::errors::CustomError;
+use crate::models::{ApiKeyFingerprint, SettingsDB};
+use crate::repository::{db_approve_change_request, db_reject_change_request};
+
+pub async fn approve_change_request(
+    db: web::Data<SettingsDB>,
+    fingerprint: ApiKeyFingerprint,
+    id: web::Path<String>,
+) -> Result<HttpResponse, CustomError> {
+    let Some(change_request) = db_approve_change_request(&db, &id, &fingerprint.0)? else {
+        return Err(CustomError::NotFoundError(format!("Change request '{}' not found", id)));
+    };
+    Ok(HttpResponse::Ok().json(change_request.masked()))
+}
+
+pub async fn reject_change_request(
+    db: web::Data<SettingsDB>,
+    fingerprint: ApiKeyFingerprint,
+    id: web::Path<String>,
+) -> Result<HttpResponse, CustomError> {
+    let Some(change_request) = db_reject_change_request(&db, &id, &fingerprint.0)? else {
+        return Err(CustomError::NotFoundError(format!("Change request '{}' not found", id)));
+    };
+    Ok(HttpResponse::Ok().json(change_request.masked()))
+}
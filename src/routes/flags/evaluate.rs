@@ -0,0 +1,52 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::enums::SettingsValueType;
+use crate::errors::CustomError;
+use crate::models::{
+    EvaluateFlagRequest, EvaluateFlagsRequest, FlagDefinition, FlagEvaluationResponse, FlagsEvaluationResponse,
+    SettingsDB
+};
+use crate::repository::{db_get_settings, db_get_settings_by_key};
+use crate::utils::evaluate_flag;
+
+pub async fn evaluate_flag_by_key(
+    db: web::Data<SettingsDB>,
+    key: web::Path<String>,
+    payload: web::Json<EvaluateFlagRequest>,
+) -> Result<HttpResponse, CustomError> {
+    let Some(settings) = db_get_settings_by_key(&db, &key)? else {
+        return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found", key)));
+    };
+    if settings.value_type != SettingsValueType::Flag {
+        return Err(CustomError::ValidationError(format!("Settings with key '{}' is not a flag", key)));
+    }
+
+    let definition = FlagDefinition::parse(&settings.value)?;
+    let response = FlagEvaluationResponse {
+        evaluation: evaluate_flag(&settings.key, &definition, &payload.subject, &payload.context),
+        key: settings.key,
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub async fn evaluate_flags(
+    db: web::Data<SettingsDB>,
+    payload: web::Json<EvaluateFlagsRequest>,
+) -> Result<HttpResponse, CustomError> {
+    let payload = payload.into_inner();
+
+    let (settings, corrupt_keys) = db_get_settings(&db, payload.prefix)?;
+    let items = settings
+        .into_iter()
+        .filter(|settings| settings.value_type == SettingsValueType::Flag)
+        .map(|settings| {
+            let definition = FlagDefinition::parse(&settings.value)?;
+            Ok(FlagEvaluationResponse {
+                evaluation: evaluate_flag(&settings.key, &definition, &payload.subject, &payload.context),
+                key: settings.key,
+            })
+        })
+        .collect::<Result<Vec<FlagEvaluationResponse>, CustomError>>()?;
+    Ok(HttpResponse::Ok().json(FlagsEvaluationResponse { items, corrupt_keys }))
+}
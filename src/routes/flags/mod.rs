@@ -0,0 +1,4 @@
+mod evaluate;
+
+pub use evaluate::evaluate_flag_by_key;
+pub use evaluate::evaluate_flags;
@@ -1,8 +1,14 @@
 mod health_check;
 mod settings;
+mod flags;
+mod change_requests;
+mod admin;
 
 mod auth;
 
 pub use health_check::*;
 pub use settings::*;
+pub use flags::*;
+pub use change_requests::*;
+pub use admin::*;
 pub use auth::*;
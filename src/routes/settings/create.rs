@@ -1,17 +1,30 @@
 use actix_web::web;
 use actix_web::HttpResponse;
 
+use crate::config::Config;
+use crate::enums::ApiKeyScope;
 use crate::errors::CustomError;
-use crate::models::{MessageResponse, CreateSettingsRequest, SettingsDBRow, SettingsDB};
-use crate::repository::db_create_settings;
+use crate::models::{
+    ApiKeyFingerprint, MessageResponse, CreateSettingsQueryParams, CreateSettingsRequest, SettingsDBRow, SettingsDB
+};
+use crate::repository::{db_create_change_request, db_create_settings, db_upsert_settings};
+use super::update::require_admin_to_reveal_secret;
 
 pub async fn create_settings(
     db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    scope: ApiKeyScope,
+    fingerprint: ApiKeyFingerprint,
+    query: web::Query<CreateSettingsQueryParams>,
     payload: web::Json<CreateSettingsRequest>,
 ) -> Result<HttpResponse, CustomError> {
     let settings = payload.into_inner();
     settings.validate()?;
 
+    if query.upsert {
+        return upsert_settings(&db, &config, &scope, &fingerprint, settings);
+    }
+
     let settings_row: SettingsDBRow = settings.into();
     let key = db_create_settings(&db, &settings_row)?;
 
@@ -25,3 +38,42 @@ pub async fn create_settings(
     };
     Ok(HttpResponse::Created().json(response))
 }
+
+fn upsert_settings(
+    db: &SettingsDB,
+    config: &Config,
+    scope: &ApiKeyScope,
+    fingerprint: &ApiKeyFingerprint,
+    settings: CreateSettingsRequest,
+) -> Result<HttpResponse, CustomError> {
+    require_admin_to_reveal_secret(db, scope, &settings.key, Some(&settings.value_type))?;
+
+    // updates of existing protected settings go live only after an approval
+    if config.is_protected(&settings.key) {
+        if let Some(change_request) = db_create_change_request(
+            db, &settings.key, Some(settings.as_update()), &fingerprint.0
+        )? {
+            return Ok(HttpResponse::Accepted().json(change_request.masked()));
+        }
+        // created only, so that settings created by someone else in the meantime are not updated without an approval
+        let settings_row: SettingsDBRow = settings.into();
+        if db_create_settings(db, &settings_row)?.is_none() {
+            return Err(CustomError::ConflictError(
+                format!("Settings with key '{}' already exist", settings_row.key)
+            ));
+        }
+        return Ok(HttpResponse::Created().json(MessageResponse {
+            message: format!("Settings with key '{}' created", settings_row.key),
+        }));
+    }
+
+    let (settings_row, created) = db_upsert_settings(db, &settings)?;
+    match created {
+        true => Ok(HttpResponse::Created().json(MessageResponse {
+            message: format!("Settings with key '{}' created", settings_row.key),
+        })),
+        false => Ok(HttpResponse::Ok().json(MessageResponse {
+            message: format!("Settings with key '{}' updated", settings_row.key),
+        })),
+    }
+}
@@ -1,19 +1,73 @@
 use actix_web::web;
 use actix_web::HttpResponse;
 
+use crate::config::Config;
+use crate::enums::DeleteOutcome;
 use crate::errors::CustomError;
-use crate::models::{MessageResponse, SettingsDB, DeleteSettingsByKeysRequest};
-use crate::repository::db_delete_settings_by_keys;
+use crate::models::{
+    ApiKeyFingerprint, ChangeRequestDBRow, DeleteSettingsResponse, DeleteSettingsResult, SettingsDB,
+    DeleteSettingsByKeysRequest, DeleteSettingsByPrefixQueryParams
+};
+use crate::repository::{db_delete_settings_by_keys, db_delete_settings_by_prefix};
 
 pub async fn delete_settings(
     db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    fingerprint: ApiKeyFingerprint,
     payload: web::Json<DeleteSettingsByKeysRequest>,
 ) -> Result<HttpResponse, CustomError> {
     let setting_keys = payload.into_inner();
 
-    db_delete_settings_by_keys(&db, setting_keys.keys)?;
-    let response = MessageResponse {
-        message: "Settings deleted".to_string(),
+    let (results, change_requests) = db_delete_settings_by_keys(
+        &db,
+        setting_keys.keys,
+        |key| config.is_protected(key),
+        &fingerprint.0,
+        setting_keys.strict,
+    )?;
+    let change_requests: Vec<_> = change_requests.into_iter().map(ChangeRequestDBRow::masked).collect();
+
+    if !change_requests.is_empty() {
+        let response = DeleteSettingsResponse {
+            message: "Settings deleted, protected settings are waiting for approval".to_string(),
+            results,
+            change_requests,
+        };
+        return Ok(HttpResponse::Accepted().json(response));
+    }
+
+    let deleted = results.iter().filter(|result| result.outcome == DeleteOutcome::Deleted).count();
+    let response = DeleteSettingsResponse {
+        message: format!("{} of {} settings deleted", deleted, results.len()),
+        results,
+        change_requests,
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub async fn delete_settings_by_prefix(
+    db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    query: web::Query<DeleteSettingsByPrefixQueryParams>,
+) -> Result<HttpResponse, CustomError> {
+    let query = query.into_inner();
+    query.validate()?;
+
+    // protected settings are deleted only through change requests, one key at a time
+    if config.is_prefix_protected(&query.prefix) {
+        return Err(CustomError::ForbiddenError(
+            "Protected settings can not be deleted by prefix".to_string()
+        ));
+    }
+
+    let keys = db_delete_settings_by_prefix(&db, &query.prefix, query.confirm)?;
+    let response = DeleteSettingsResponse {
+        message: format!("{} settings deleted", keys.len()),
+        results: keys
+            .into_iter()
+            .map(|key| DeleteSettingsResult { key, outcome: DeleteOutcome::Deleted })
+            .collect(),
+        change_requests: Vec::new(),
     };
     Ok(HttpResponse::Ok().json(response))
 }
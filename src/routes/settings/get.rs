@@ -1,9 +1,16 @@
+use actix_web::http::header::HeaderValue;
 use actix_web::web;
 use actix_web::HttpResponse;
 
+use crate::enums::{ApiKeyScope, SettingsValueType};
 use crate::errors::CustomError;
-use crate::models::{SettingsDB, GetSettingsQueryParams};
-use crate::repository::{db_get_settings, db_get_settings_by_key};
+use crate::models::{
+    SettingsDB, SettingsDBRow, GetSettingsByKeysRequest, GetSettingsQueryParams, SearchSettingsQueryParams
+};
+use crate::repository::{
+    db_get_settings_by_key, db_get_settings_by_keys, db_get_settings_list, db_get_settings_page, db_search_settings
+};
+use crate::utils::SettingsSearch;
 
 pub async fn get_settings_by_key(
     db: web::Data<SettingsDB>,
@@ -13,13 +20,87 @@ pub async fn get_settings_by_key(
     if settings_row.is_none() {
         return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found",  key)));
     }
-    Ok(HttpResponse::Ok().json(settings_row))
+    Ok(HttpResponse::Ok().json(settings_row.map(SettingsDBRow::masked)))
+}
+
+/// The plain array has no room for the `corruptKeys` of the pages, they are sent in this header instead
+const CORRUPT_KEYS_HEADER: &str = "X-Corrupt-Keys";
+
+/// JSON array of the keys, escaping the characters that are not allowed in a header value
+fn corrupt_keys_header(corrupt_keys: &[String]) -> Result<HeaderValue, CustomError> {
+    let keys = serde_json::to_string(corrupt_keys)
+        .map_err(|err| CustomError::InternalError(err.to_string()))?
+        .replace('\u{7f}', "\\u007f");
+    HeaderValue::from_bytes(keys.as_bytes())
+        .map_err(|err| CustomError::InternalError(err.to_string()))
 }
 
 pub async fn get_settings(
     db: web::Data<SettingsDB>,
     query: web::Query<GetSettingsQueryParams>,
 ) -> Result<HttpResponse, CustomError> {
-    let settings_rows = db_get_settings(&db, query.prefix.clone())?;
-    Ok(HttpResponse::Ok().json(settings_rows))
+    query.validate()?;
+
+    if !query.is_paginated() {
+        let (settings_rows, corrupt_keys) = db_get_settings_list(&db, &query)?;
+        let settings_rows: Vec<SettingsDBRow> = settings_rows.into_iter().map(SettingsDBRow::masked).collect();
+        let mut response = HttpResponse::Ok();
+        if !corrupt_keys.is_empty() {
+            response.insert_header((CORRUPT_KEYS_HEADER, corrupt_keys_header(&corrupt_keys)?));
+        }
+        return Ok(response.json(settings_rows));
+    }
+
+    let mut page = db_get_settings_page(&db, &query)?;
+    page.items = page.items.into_iter().map(SettingsDBRow::masked).collect();
+    Ok(HttpResponse::Ok().json(page))
+}
+
+pub async fn get_settings_by_keys(
+    db: web::Data<SettingsDB>,
+    payload: web::Json<GetSettingsByKeysRequest>,
+) -> Result<HttpResponse, CustomError> {
+    payload.validate()?;
+
+    let mut response = db_get_settings_by_keys(&db, &payload.keys)?;
+    response.found = response.found.into_iter().map(SettingsDBRow::masked).collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub async fn search_settings(
+    db: web::Data<SettingsDB>,
+    query: web::Query<SearchSettingsQueryParams>,
+) -> Result<HttpResponse, CustomError> {
+    query.validate()?;
+    let search = SettingsSearch::new(&query.q, query.regex)?;
+
+    let mut response = db_search_settings(&db, &search, query.value_type.as_ref(), query.limit)?;
+    response.items = response.items.into_iter().map(SettingsDBRow::masked).collect();
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub async fn reveal_settings_by_key(
+    db: web::Data<SettingsDB>,
+    scope: ApiKeyScope,
+    key: web::Path<String>,
+) -> Result<HttpResponse, CustomError> {
+    if let Err(err) = scope.require_admin() {
+        log::warn!(target: "audit", "Denied to reveal settings with key '{}': not an admin api key", key);
+        return Err(err);
+    }
+
+    let Some(mut settings_row) = db_get_settings_by_key(&db, &key)? else {
+        return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found",  key)));
+    };
+    if settings_row.value_type == SettingsValueType::Secret {
+        settings_row.value = db.cipher.decrypt(&settings_row.value)?;
+    }
+    if let Some(revert_to) = settings_row.revert_to.as_mut() {
+        if revert_to.value_type == SettingsValueType::Secret {
+            revert_to.value = db.cipher.decrypt(&revert_to.value)?;
+        }
+    }
+
+    log::info!(target: "audit", "Settings with key '{}' revealed with admin api key", key);
+    Ok(HttpResponse::Ok().json(settings_row))
 }
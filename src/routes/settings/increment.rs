@@ -0,0 +1,26 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::config::Config;
+use crate::errors::CustomError;
+use crate::models::{IncrementSettingsRequest, SettingsDB};
+use crate::repository::db_increment_settings;
+
+pub async fn increment_settings(
+    db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    key: web::Path<String>,
+    payload: web::Json<IncrementSettingsRequest>,
+) -> Result<HttpResponse, CustomError> {
+    // a change request would hold a stale value by the time it is approved
+    if config.is_protected(&key) {
+        return Err(CustomError::ForbiddenError(format!(
+            "Settings with key '{}' is protected, changes to it require an approval", key
+        )));
+    }
+
+    let Some(settings_row) = db_increment_settings(&db, &key, &payload)? else {
+        return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found",  key)));
+    };
+    Ok(HttpResponse::Ok().json(settings_row.masked()))
+}
@@ -0,0 +1,41 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::enums::ApiKeyScope;
+use crate::errors::CustomError;
+use crate::repository::db_set_settings_locked;
+use crate::models::SettingsDB;
+
+pub async fn lock_settings(
+    db: web::Data<SettingsDB>,
+    scope: ApiKeyScope,
+    key: web::Path<String>,
+) -> Result<HttpResponse, CustomError> {
+    set_settings_locked(&db, scope, &key, true)
+}
+
+pub async fn unlock_settings(
+    db: web::Data<SettingsDB>,
+    scope: ApiKeyScope,
+    key: web::Path<String>,
+) -> Result<HttpResponse, CustomError> {
+    set_settings_locked(&db, scope, &key, false)
+}
+
+fn set_settings_locked(
+    db: &SettingsDB,
+    scope: ApiKeyScope,
+    key: &str,
+    locked: bool,
+) -> Result<HttpResponse, CustomError> {
+    scope.require_admin()?;
+
+    let Some(settings_row) = db_set_settings_locked(db, key, locked)? else {
+        return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found",  key)));
+    };
+    log::info!(
+        target: "audit",
+        "Settings with key '{}' {} with admin api key", key, if locked { "locked" } else { "unlocked" }
+    );
+    Ok(HttpResponse::Ok().json(settings_row.masked()))
+}
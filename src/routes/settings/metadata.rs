@@ -0,0 +1,20 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::errors::CustomError;
+use crate::models::{SettingsDB, UpdateSettingsMetadataRequest};
+use crate::repository::db_update_settings_metadata;
+
+pub async fn update_settings_metadata(
+    db: web::Data<SettingsDB>,
+    key: web::Path<String>,
+    payload: web::Json<UpdateSettingsMetadataRequest>,
+) -> Result<HttpResponse, CustomError> {
+    let metadata_payload = payload.into_inner();
+    metadata_payload.validate()?;
+
+    let Some(settings_row) = db_update_settings_metadata(&db, &key, &metadata_payload)? else {
+        return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found",  key)));
+    };
+    Ok(HttpResponse::Ok().json(settings_row.masked()))
+}
@@ -2,9 +2,33 @@ mod get;
 mod create;
 mod delete;
 mod update;
+mod schedule;
+mod lock;
+mod metadata;
+mod transaction;
+mod moves;
+mod patch;
+mod increment;
+mod trash;
 
 pub use get::get_settings;
 pub use get::get_settings_by_key;
+pub use get::get_settings_by_keys;
+pub use get::search_settings;
+pub use get::reveal_settings_by_key;
 pub use create::create_settings;
-pub use delete::delete_settings;
+pub use delete::{delete_settings, delete_settings_by_prefix};
 pub use update::update_settings;
+pub use schedule::schedule_settings;
+pub use schedule::get_scheduled_changes;
+pub use schedule::cancel_scheduled_change;
+pub use lock::lock_settings;
+pub use lock::unlock_settings;
+pub use metadata::update_settings_metadata;
+pub use transaction::run_settings_transaction;
+pub use moves::rename_settings;
+pub use moves::copy_settings;
+pub use patch::patch_settings;
+pub use increment::increment_settings;
+pub use trash::get_trash;
+pub use trash::restore_settings;
@@ -0,0 +1,56 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::config::Config;
+use crate::errors::CustomError;
+use crate::models::{MoveSettingsRequest, MoveSettingsResponse, SettingsDB};
+use crate::repository::{db_copy_settings, db_rename_settings};
+
+pub async fn rename_settings(
+    db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    payload: web::Json<MoveSettingsRequest>,
+) -> Result<HttpResponse, CustomError> {
+    let move_payload = payload.into_inner();
+    validate_move(&config, &move_payload)?;
+
+    let keys = db_rename_settings(&db, &move_payload)?;
+    let response = MoveSettingsResponse {
+        message: format!("{} settings renamed", keys.len()),
+        keys,
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub async fn copy_settings(
+    db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    payload: web::Json<MoveSettingsRequest>,
+) -> Result<HttpResponse, CustomError> {
+    let move_payload = payload.into_inner();
+    validate_move(&config, &move_payload)?;
+
+    let keys = db_copy_settings(&db, &move_payload)?;
+    let response = MoveSettingsResponse {
+        message: format!("{} settings copied", keys.len()),
+        keys,
+    };
+    Ok(HttpResponse::Created().json(response))
+}
+
+/// Protected settings are changed only through change requests,
+/// so they can be neither moved nor overwritten by a move
+fn validate_move(config: &Config, payload: &MoveSettingsRequest) -> Result<(), CustomError> {
+    payload.validate()?;
+
+    let protected = match payload.prefix {
+        true => config.is_prefix_protected(&payload.from) || config.is_prefix_protected(&payload.to),
+        false => config.is_protected(&payload.from) || config.is_protected(&payload.to),
+    };
+    if protected {
+        return Err(CustomError::ForbiddenError(
+            "Protected settings can not be renamed or copied".to_string()
+        ));
+    }
+    Ok(())
+}
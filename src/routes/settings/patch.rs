@@ -0,0 +1,47 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_web::http::header::CONTENT_TYPE;
+
+use crate::config::Config;
+use crate::errors::CustomError;
+use crate::models::{ApiKeyFingerprint, SettingsDB, SettingsPatch, UpdateSettingsRequest};
+use crate::repository::{db_create_change_request, db_get_settings_by_key, db_patch_settings};
+
+pub async fn patch_settings(
+    db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    fingerprint: ApiKeyFingerprint,
+    key: web::Path<String>,
+    request: HttpRequest,
+    body: web::Bytes,
+) -> Result<HttpResponse, CustomError> {
+    let content_type = request.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        // without parameters like `charset`
+        .and_then(|content_type| content_type.split(';').next())
+        .unwrap_or_default()
+        .trim();
+    let patch = SettingsPatch::parse(content_type, &body)?;
+
+    // updates of protected settings go live only after an approval,
+    // the change request holds the whole patched value
+    if config.is_protected(&key) {
+        let Some(settings) = db_get_settings_by_key(&db, &key)? else {
+            return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found",  key)));
+        };
+        let update = UpdateSettingsRequest {
+            key: key.to_string(),
+            value: Some(patch.apply(&settings)?),
+            ..Default::default()
+        };
+        let Some(change_request) = db_create_change_request(&db, &key, Some(update), &fingerprint.0)? else {
+            return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found",  key)));
+        };
+        return Ok(HttpResponse::Accepted().json(change_request.masked()));
+    }
+
+    let Some(settings_row) = db_patch_settings(&db, &key, &patch)? else {
+        return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found",  key)));
+    };
+    Ok(HttpResponse::Ok().json(settings_row.masked()))
+}
@@ -0,0 +1,61 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::config::Config;
+use crate::errors::CustomError;
+use crate::models::{MessageResponse, ScheduleSettingsRequest, ScheduledChangeDBRow, SettingsDB};
+use crate::repository::{
+    db_create_scheduled_change, db_delete_scheduled_change, db_get_scheduled_changes
+};
+
+pub async fn schedule_settings(
+    db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    key: web::Path<String>,
+    payload: web::Json<ScheduleSettingsRequest>,
+) -> Result<HttpResponse, CustomError> {
+    let schedule_payload = payload.into_inner();
+    schedule_payload.validate()?;
+
+    // the scheduler applies the change without an approval
+    if config.is_protected(&key) {
+        return Err(CustomError::ForbiddenError(
+            "Changes of protected settings can not be scheduled".to_string()
+        ));
+    }
+
+    let Some(scheduled_change) = db_create_scheduled_change(
+        &db, &key, &schedule_payload.value, schedule_payload.apply_at
+    )? else {
+        return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found", key)));
+    };
+    Ok(HttpResponse::Created().json(scheduled_change.masked()))
+}
+
+pub async fn get_scheduled_changes(
+    db: web::Data<SettingsDB>,
+    key: web::Path<String>,
+) -> Result<HttpResponse, CustomError> {
+    let scheduled_changes: Vec<ScheduledChangeDBRow> = db_get_scheduled_changes(&db, Some(&key))?
+        .into_iter()
+        .map(ScheduledChangeDBRow::masked)
+        .collect();
+    Ok(HttpResponse::Ok().json(scheduled_changes))
+}
+
+pub async fn cancel_scheduled_change(
+    db: web::Data<SettingsDB>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, CustomError> {
+    let (key, id) = path.into_inner();
+
+    if db_delete_scheduled_change(&db, &key, &id)?.is_none() {
+        return Err(CustomError::NotFoundError(format!(
+            "Scheduled change '{}' for settings with key '{}' not found", id, key
+        )));
+    }
+    let response = MessageResponse {
+        message: "Scheduled change cancelled".to_string(),
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
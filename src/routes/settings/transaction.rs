@@ -0,0 +1,36 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::config::Config;
+use crate::enums::ApiKeyScope;
+use crate::errors::CustomError;
+use crate::models::{SettingsDB, SettingsOperation, SettingsTransactionRequest};
+use crate::repository::db_run_settings_transaction;
+use super::update::require_admin_to_reveal_secret;
+
+pub async fn run_settings_transaction(
+    db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    scope: ApiKeyScope,
+    payload: web::Json<SettingsTransactionRequest>,
+) -> Result<HttpResponse, CustomError> {
+    let transaction_payload = payload.into_inner();
+    transaction_payload.validate()?;
+
+    for operation in &transaction_payload.operations {
+        // protected settings are changed only through change requests
+        if config.is_protected(operation.key()) {
+            return Err(CustomError::ForbiddenError(format!(
+                "Settings with key '{}' is protected, changes to it require an approval",
+                operation.key()
+            )));
+        }
+
+        if let SettingsOperation::Update(update) = operation {
+            require_admin_to_reveal_secret(&db, &scope, &update.key, update.value_type.as_ref())?;
+        }
+    }
+
+    let response = db_run_settings_transaction(&db, &transaction_payload)?;
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -0,0 +1,37 @@
+use actix_web::web;
+use actix_web::HttpResponse;
+
+use crate::config::Config;
+use crate::errors::CustomError;
+use crate::models::{RestoreSettingsRequest, RestoreSettingsResponse, SettingsDB, TrashedSettingsDBRow};
+use crate::repository::{db_get_trash, db_restore_settings};
+
+pub async fn get_trash(
+    db: web::Data<SettingsDB>,
+) -> Result<HttpResponse, CustomError> {
+    let trashed: Vec<TrashedSettingsDBRow> = db_get_trash(&db)?
+        .into_iter()
+        .map(TrashedSettingsDBRow::masked)
+        .collect();
+    Ok(HttpResponse::Ok().json(trashed))
+}
+
+pub async fn restore_settings(
+    db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    payload: web::Json<RestoreSettingsRequest>,
+) -> Result<HttpResponse, CustomError> {
+    // a restore would bring back a protected settings whose delete was approved
+    if let Some(key) = payload.keys.iter().find(|key| config.is_protected(key)) {
+        return Err(CustomError::ForbiddenError(format!(
+            "Settings with key '{}' is protected and can not be restored", key
+        )));
+    }
+
+    let restored = db_restore_settings(&db, &payload.keys)?;
+    let response = RestoreSettingsResponse {
+        message: "Settings restored".to_string(),
+        restored,
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -1,21 +1,46 @@
 use actix_web::web;
 use actix_web::HttpResponse;
 
+use crate::config::Config;
+use crate::enums::{ApiKeyScope, SettingsValueType};
 use crate::errors::CustomError;
-use crate::models::{MessageResponse, SettingsDB, UpdateSettingsRequest};
-use crate::repository::{db_update_settings_by_key};
+use crate::models::{ApiKeyFingerprint, MessageResponse, SettingsDB, UpdateSettingsRequest};
+use crate::repository::{
+    db_check_settings_update, db_create_change_request, db_get_settings_by_key, db_update_settings_by_key
+};
 
 pub async fn update_settings(
     db: web::Data<SettingsDB>,
+    config: web::Data<Config>,
+    scope: ApiKeyScope,
+    fingerprint: ApiKeyFingerprint,
     payload: web::Json<UpdateSettingsRequest>,
 ) -> Result<HttpResponse, CustomError> {
     let update_setting_payload = payload.into_inner();
     update_setting_payload.validate()?;
 
-    let key = db_update_settings_by_key(
-        &db, &update_setting_payload.key, &update_setting_payload.value
+    // checked before the dry run too, as whether a secret is convertible tells about its value
+    require_admin_to_reveal_secret(
+        &db, &scope, &update_setting_payload.key, update_setting_payload.value_type.as_ref()
     )?;
 
+    if update_setting_payload.dry_run {
+        return update_settings_dry_run(&db, update_setting_payload);
+    }
+
+    // updates of protected settings go live only after an approval
+    if config.is_protected(&update_setting_payload.key) {
+        let key = update_setting_payload.key.clone();
+        let Some(change_request) = db_create_change_request(
+            &db, &key, Some(update_setting_payload), &fingerprint.0
+        )? else {
+            return Err(CustomError::NotFoundError(format!("Settings with key '{}' not found", key)));
+        };
+        return Ok(HttpResponse::Accepted().json(change_request.masked()));
+    }
+
+    let key = db_update_settings_by_key(&db, &update_setting_payload)?;
+
     if key.is_none() {
         return Err(CustomError::NotFoundError(
             format!("Settings with key '{}' not found", update_setting_payload.key)
@@ -27,3 +52,39 @@ pub async fn update_settings(
     };
     Ok(HttpResponse::Ok().json(response))
 }
+
+/// Turning a secret into a plain type reveals it, so it is allowed only for admins
+pub(super) fn require_admin_to_reveal_secret(
+    db: &SettingsDB,
+    scope: &ApiKeyScope,
+    key: &str,
+    value_type: Option<&SettingsValueType>,
+) -> Result<(), CustomError> {
+    if value_type.is_some_and(|t| *t != SettingsValueType::Secret) {
+        let settings = db_get_settings_by_key(db, key)?;
+        if settings.is_some_and(|s| s.value_type == SettingsValueType::Secret) {
+            scope.require_admin()?;
+        }
+    }
+    Ok(())
+}
+
+fn update_settings_dry_run(
+    db: &SettingsDB,
+    payload: UpdateSettingsRequest,
+) -> Result<HttpResponse, CustomError> {
+    let Some(value_type) = payload.value_type else {
+        return Err(CustomError::ValidationError(
+            "`dryRun` requires `type` to be provided".to_string()
+        ));
+    };
+
+    let Some(response) = db_check_settings_update(
+        db, &payload.key, payload.value.as_ref(), value_type
+    )? else {
+        return Err(CustomError::NotFoundError(
+            format!("Settings with key '{}' not found", payload.key)
+        ))
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -5,26 +5,59 @@ use actix_web::{
     body::MessageBody,
     dev::{Server, ServiceRequest, ServiceResponse},
     middleware::{from_fn, Next, Logger},
-    web, App, Error, HttpServer
+    guard::{self, GuardContext},
+    web, App, Error, HttpMessage, HttpServer
 };
 use actix_cors::Cors;
 use actix_files;
 use env_logger::Env;
 
 use crate::{
+    config::Config,
+    enums::ApiKeyScope,
     errors::CustomError,
-    models::SettingsDB,
+    models::{ApiKeyFingerprint, SettingsDB},
     routes::{
         create_settings,
         get_settings,
         get_settings_by_key,
+        get_settings_by_keys,
+        search_settings,
+        reveal_settings_by_key,
+        schedule_settings,
+        get_scheduled_changes,
+        cancel_scheduled_change,
+        lock_settings,
+        unlock_settings,
+        update_settings_metadata,
+        run_settings_transaction,
+        rename_settings,
+        copy_settings,
+        patch_settings,
+        increment_settings,
+        get_trash,
+        restore_settings,
         delete_settings,
+        delete_settings_by_prefix,
         update_settings,
+        evaluate_flag_by_key,
+        evaluate_flags,
         health_check,
-        validate_token
+        validate_token,
+        get_change_requests,
+        approve_change_request,
+        reject_change_request,
+        check_integrity,
+        download_backup
     },
+    repository::{db_index_expirations, db_migrate_settings},
+    tasks::{run_expiry_sweeper, run_scheduler, run_trash_purger},
 };
 
+fn has_prefix_query(ctx: &GuardContext) -> bool {
+    ctx.head().uri.query().is_some_and(|query| query.split('&').any(|param| param.starts_with("prefix=")))
+}
+
 async fn fallback_index() -> Result<actix_files::NamedFile> {
     Ok(actix_files::NamedFile::open("./ui/dist/index.html")?)
 }
@@ -54,22 +87,45 @@ async fn auth_middleware(
         return Err(CustomError::UnauthorizedError("missing `X-Api-Key` header".to_string()).into());
     }
 
-    let expected_api_key = req.app_data::<web::Data<String>>().unwrap();
-    if api_key_header.unwrap().to_str().unwrap() != expected_api_key.to_string() {
+    let config = req.app_data::<web::Data<Config>>().unwrap();
+    let api_key = api_key_header.unwrap().to_str().unwrap_or_default();
+    let scope = if api_key == config.admin_api_key {
+        ApiKeyScope::Admin
+    } else if api_key == config.api_key {
+        ApiKeyScope::Regular
+    } else {
         return Err(CustomError::ForbiddenError("invalid `X-Api-Key` header".to_string()).into());
-    }
+    };
+
+    let fingerprint = ApiKeyFingerprint::of(api_key);
+    req.extensions_mut().insert(scope);
+    req.extensions_mut().insert(fingerprint);
     next.call(req).await
 }
 
 pub fn run(
     listener: TcpListener,
     db: SettingsDB,
-    api_key: String,
+    config: Config,
 ) -> Result<Server, std::io::Error> {
     let db = web::Data::new(db);
-    let api_key = web::Data::new(api_key);
+    let config = web::Data::new(config);
     env_logger::init_from_env(Env::default().default_filter_or("info"));
 
+    // before serving, so that no request reads the old rows while they are rewritten
+    let migrated = db_migrate_settings(&db)
+        .map_err(|err| std::io::Error::other(format!("Failed to migrate settings: {}", err)))?;
+    if migrated > 0 {
+        log::info!("Migrated {} settings to the current format", migrated);
+    }
+    let indexed = db_index_expirations(&db)
+        .map_err(|err| std::io::Error::other(format!("Failed to index expiring settings: {}", err)))?;
+    log::info!("Indexed {} expiring settings", indexed);
+
+    tokio::spawn(run_scheduler(db.clone(), config.clone()));
+    tokio::spawn(run_expiry_sweeper(db.clone()));
+    tokio::spawn(run_trash_purger(db.clone(), config.trash_retention_days));
+
     let server = HttpServer::new(move || {
         let cors = Cors::permissive();
 
@@ -83,9 +139,47 @@ pub fn run(
                         .wrap(from_fn(auth_middleware))
                         .route("", web::post().to(create_settings))
                         .route("", web::get().to(get_settings))
+                        // `?prefix=` deletes by prefix instead of the keys from the body
+                        .route("", web::delete().guard(guard::fn_guard(has_prefix_query)).to(delete_settings_by_prefix))
                         .route("", web::delete().to(delete_settings))
                         .route("", web::put().to(update_settings))
+                        .route("/batch-get", web::post().to(get_settings_by_keys))
+                        .route("/transaction", web::post().to(run_settings_transaction))
+                        .route("/rename", web::post().to(rename_settings))
+                        .route("/copy", web::post().to(copy_settings))
+                        // before `/{key}`, so that `search` and `trash` are not taken for keys
+                        .route("/search", web::get().to(search_settings))
+                        .route("/trash", web::get().to(get_trash))
+                        .route("/trash/restore", web::post().to(restore_settings))
                         .route("/{key}", web::get().to(get_settings_by_key))
+                        .route("/{key}", web::patch().to(patch_settings))
+                        .route("/{key}/reveal", web::get().to(reveal_settings_by_key))
+                        .route("/{key}/schedule", web::post().to(schedule_settings))
+                        .route("/{key}/schedule", web::get().to(get_scheduled_changes))
+                        .route("/{key}/schedule/{id}", web::delete().to(cancel_scheduled_change))
+                        .route("/{key}/increment", web::post().to(increment_settings))
+                        .route("/{key}/lock", web::post().to(lock_settings))
+                        .route("/{key}/unlock", web::post().to(unlock_settings))
+                        .route("/{key}/metadata", web::put().to(update_settings_metadata))
+                )
+                .service(
+                    web::scope("/flags")
+                        .wrap(from_fn(auth_middleware))
+                        .route("/evaluate", web::post().to(evaluate_flags))
+                        .route("/{key}/evaluate", web::post().to(evaluate_flag_by_key))
+                )
+                .service(
+                    web::scope("/change-requests")
+                        .wrap(from_fn(auth_middleware))
+                        .route("", web::get().to(get_change_requests))
+                        .route("/{id}/approve", web::post().to(approve_change_request))
+                        .route("/{id}/reject", web::post().to(reject_change_request))
+                )
+                .service(
+                    web::scope("/admin")
+                        .wrap(from_fn(auth_middleware))
+                        .route("/integrity", web::get().to(check_integrity))
+                        .route("/backup", web::get().to(download_backup))
                 )
                 .service(
                     web::scope("/auth")
@@ -93,7 +187,7 @@ pub fn run(
                 )
             )
             .route("/health", web::get().to(health_check))
-            .app_data(api_key.clone())
+            .app_data(config.clone())
             .app_data(db.clone())
             .configure(json_error_handler);
 
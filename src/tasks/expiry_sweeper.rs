@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use actix_web::web;
+use chrono::Utc;
+
+use crate::models::SettingsDB;
+use crate::repository::db_expire_settings;
+
+const EXPIRY_SWEEPER_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Reverts or deletes temporary overrides once their `expiresAt` is reached.
+/// The sweep syncs its writes to disk, so it runs on the blocking threads.
+pub async fn run_expiry_sweeper(db: web::Data<SettingsDB>) {
+    let mut interval = tokio::time::interval(EXPIRY_SWEEPER_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        let db = db.clone();
+        match tokio::task::spawn_blocking(move || db_expire_settings(&db, Utc::now())).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(err)) => log::error!("Failed to expire settings: {}", err),
+            Err(err) => log::error!("Expiry sweep failed: {}", err),
+        }
+    }
+}
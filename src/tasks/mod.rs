@@ -0,0 +1,7 @@
+mod expiry_sweeper;
+mod scheduler;
+mod trash_purger;
+
+pub use expiry_sweeper::run_expiry_sweeper;
+pub use scheduler::run_scheduler;
+pub use trash_purger::run_trash_purger;
@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use actix_web::web;
+use chrono::Utc;
+
+use crate::config::Config;
+use crate::models::SettingsDB;
+use crate::repository::db_apply_due_scheduled_changes;
+
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Applies scheduled settings changes once they are due.
+/// Changes are stored in the db, so the ones missed while the server was down
+/// are applied on the first tick after a restart.
+pub async fn run_scheduler(db: web::Data<SettingsDB>, config: web::Data<Config>) {
+    let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = db_apply_due_scheduled_changes(&db, Utc::now(), |key| config.is_protected(key)) {
+            log::error!("Failed to apply scheduled changes: {}", err);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use actix_web::web;
+use chrono::{TimeDelta, Utc};
+
+use crate::models::SettingsDB;
+use crate::repository::db_purge_trash;
+
+const TRASH_PURGER_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Permanently deletes the settings kept in the trash for longer than the retention period
+pub async fn run_trash_purger(db: web::Data<SettingsDB>, retention_days: u32) {
+    let retention = TimeDelta::days(retention_days.into());
+    let mut interval = tokio::time::interval(TRASH_PURGER_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = db_purge_trash(&db, Utc::now() - retention) {
+            log::error!("Failed to purge the trash: {}", err);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::enums::FlagEvaluationReason;
+use crate::models::{FlagDefinition, FlagEvaluation, FlagVariant};
+
+const FLAG_BUCKETS: u64 = 100;
+
+/// Stable bucket in `0..100` for a subject of a flag: the same subject always
+/// lands in the same bucket, so it keeps its result while the rollout grows.
+pub fn flag_bucket(key: &str, subject: &str) -> u8 {
+    let digest = Sha256::digest(format!("{}:{}", key, subject).as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    (u64::from_be_bytes(bytes) % FLAG_BUCKETS) as u8
+}
+
+pub fn evaluate_flag(
+    key: &str,
+    definition: &FlagDefinition,
+    subject: &str,
+    context: &Value,
+) -> FlagEvaluation {
+    if let Some((index, rule)) = definition.rules
+        .iter()
+        .enumerate()
+        .find(|(_, rule)| rule.matches(context)) {
+        let variant = match &rule.variant {
+            Some(name) if rule.result => definition.variant(name),
+            Some(_) => None,
+            None if rule.result => pick_variant(key, definition, subject),
+            None => None,
+        };
+        return evaluation(rule.result, FlagEvaluationReason::Rule, Some(index), variant);
+    }
+
+    let enabled = flag_bucket(key, subject) < definition.rollout;
+    let variant = match enabled {
+        true => pick_variant(key, definition, subject),
+        false => None,
+    };
+    evaluation(enabled, FlagEvaluationReason::Rollout, None, variant)
+}
+
+/// Variants get their own bucket, so that the split between them
+/// does not depend on the rollout percentage.
+fn pick_variant<'a>(
+    key: &str,
+    definition: &'a FlagDefinition,
+    subject: &str,
+) -> Option<&'a FlagVariant> {
+    let bucket = flag_bucket(&format!("{}#variants", key), subject);
+
+    let mut upper_bound: u32 = 0;
+    definition.variants.iter().find(|variant| {
+        upper_bound += variant.weight as u32;
+        (bucket as u32) < upper_bound
+    })
+}
+
+fn evaluation(
+    enabled: bool,
+    reason: FlagEvaluationReason,
+    rule_index: Option<usize>,
+    variant: Option<&FlagVariant>,
+) -> FlagEvaluation {
+    FlagEvaluation {
+        enabled,
+        reason,
+        rule_index,
+        variant: variant.map(|variant| variant.name.clone()),
+        payload: variant.map(|variant| variant.payload.clone()),
+    }
+}
@@ -1,3 +1,9 @@
+mod evaluate_flag;
+mod search_settings;
+mod secret_cipher;
 mod validate_settings_value;
 
+pub use evaluate_flag::{evaluate_flag, flag_bucket};
+pub use search_settings::{SearchRank, SettingsSearch, MAX_REGEX_SIZE};
+pub use secret_cipher::SecretCipher;
 pub use validate_settings_value::validate_settings_value;
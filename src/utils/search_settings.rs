@@ -0,0 +1,77 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::enums::SettingsValueType;
+use crate::errors::CustomError;
+use crate::models::SettingsDBRow;
+
+/// Limit of the compiled size of the regexes given by the clients
+pub const MAX_REGEX_SIZE: usize = 1_024 * 1_024;
+
+/// Rank of a settings found by the search, lower ranks go first
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum SearchRank {
+    ExactKey,
+    Key,
+    Other,
+}
+
+enum SearchPattern {
+    /// Case-insensitive substring, stored lowercased
+    Substring(String),
+    Regex(Regex),
+}
+
+pub struct SettingsSearch {
+    pattern: SearchPattern,
+}
+
+impl SettingsSearch {
+    pub fn new(query: &str, regex: bool) -> Result<Self, CustomError> {
+        let pattern = match regex {
+            true => SearchPattern::Regex(
+                RegexBuilder::new(query)
+                    .size_limit(MAX_REGEX_SIZE)
+                    .build()
+                    .map_err(|_| CustomError::ValidationError(format!("Invalid regex '{}'", query)))?
+            ),
+            false => SearchPattern::Substring(query.to_lowercase()),
+        };
+        Ok(Self { pattern })
+    }
+
+    /// Matches the key, the value and the metadata, `None` if nothing matches.
+    /// Values of `secret` settings are never matched.
+    pub fn rank(&self, settings: &SettingsDBRow) -> Option<SearchRank> {
+        if self.is_exact(&settings.key) {
+            return Some(SearchRank::ExactKey);
+        }
+        if self.is_match(&settings.key) {
+            return Some(SearchRank::Key);
+        }
+
+        let value_matches = settings.value_type != SettingsValueType::Secret
+            && self.is_match(&settings.value);
+        let metadata_matches = settings.description.as_ref().is_some_and(|d| self.is_match(d))
+            || settings.owner.as_ref().is_some_and(|o| self.is_match(o))
+            || settings.tags.iter().any(|tag| self.is_match(tag));
+
+        (value_matches || metadata_matches).then_some(SearchRank::Other)
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match &self.pattern {
+            SearchPattern::Substring(query) => text.to_lowercase().contains(query),
+            SearchPattern::Regex(regex) => regex.is_match(text),
+        }
+    }
+
+    /// The whole key matches: equal ignoring the case, or matched entirely by the regex
+    pub fn is_exact(&self, text: &str) -> bool {
+        match &self.pattern {
+            SearchPattern::Substring(query) => text.to_lowercase() == *query,
+            SearchPattern::Regex(regex) => regex
+                .find(text)
+                .is_some_and(|found| found.start() == 0 && found.end() == text.len()),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha2::{Digest, Sha256};
+
+use crate::errors::CustomError;
+
+const NONCE_LENGTH: usize = 12;
+
+/// Encrypts values of `secret` settings before they are written to the db.
+/// Stored value is base64 of `nonce || ciphertext`.
+#[derive(Clone)]
+pub struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    pub fn new(secret_key: &str) -> Self {
+        // any passphrase from the config is turned into a 256-bit key
+        let key = Sha256::digest(secret_key.as_bytes());
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+        }
+    }
+
+    pub fn encrypt(&self, value: &str) -> Result<String, CustomError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self.cipher
+            .encrypt(&nonce, value.as_bytes())
+            .map_err(|_| CustomError::InternalError("Failed to encrypt secret".to_string()))?;
+
+        let mut bytes = nonce.to_vec();
+        bytes.extend(ciphertext);
+        Ok(STANDARD.encode(bytes))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String, CustomError> {
+        let error = || CustomError::InternalError("Failed to decrypt secret".to_string());
+
+        let bytes = STANDARD.decode(value).map_err(|_| error())?;
+        if bytes.len() < NONCE_LENGTH {
+            return Err(error());
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LENGTH);
+        let plaintext = self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| error())?;
+        String::from_utf8(plaintext).map_err(|_| error())
+    }
+}
@@ -1,12 +1,13 @@
 use crate::enums::SettingsValueType;
 use crate::errors::CustomError;
+use crate::models::FlagDefinition;
 
 pub fn validate_settings_value(
     value: String,
     value_type: SettingsValueType,
 ) -> Result<(), CustomError>  {
     match value_type {
-        SettingsValueType::Str => Ok(()),
+        SettingsValueType::Str | SettingsValueType::Secret => Ok(()),
         SettingsValueType::Int => {
             value.parse::<i64>().map_err(|_| {
                 CustomError::ValidationError(format!(
@@ -43,5 +44,9 @@ pub fn validate_settings_value(
             })?;
             Ok(())
         }
+        SettingsValueType::Flag => {
+            FlagDefinition::parse(&value)?;
+            Ok(())
+        }
     }
 }
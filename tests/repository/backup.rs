@@ -0,0 +1,100 @@
+use crate::helpers::{open_db, settings_of};
+use dynamic_settings::enums::SettingsValueType;
+use dynamic_settings::errors::CustomError;
+use dynamic_settings::repository::{
+    db_create_settings, db_delete_settings_by_keys, db_get_settings_by_key, db_get_trash, db_restore_backup,
+    db_write_backup
+};
+
+#[test]
+fn test_backup_and_restore() {
+    // Arrange
+    let (_dir, db) = open_db();
+    db_create_settings(&db, &settings_of("db.timeout", "30", SettingsValueType::Int)).unwrap().unwrap();
+    db_create_settings(&db, &settings_of("db.password", "hunter2", SettingsValueType::Secret)).unwrap().unwrap();
+    db_create_settings(&db, &settings_of("legacy", "true", SettingsValueType::Bool)).unwrap().unwrap();
+    db_delete_settings_by_keys(&db, vec!["legacy".to_string()], |_| false, "tests", false).unwrap();
+
+    let mut archive = Vec::new();
+    let manifest = db_write_backup(&db, &mut archive).unwrap();
+    assert_eq!(manifest.partitions["settings"], 2);
+    assert_eq!(manifest.partitions["trash"], 1);
+
+    let (_restored_dir, restored_db) = open_db();
+
+    // Act
+    let restored = db_restore_backup(&restored_db, archive.as_slice()).unwrap();
+
+    // Assert
+    assert_eq!(restored.partitions, manifest.partitions);
+    let settings = db_get_settings_by_key(&restored_db, "db.timeout").unwrap().unwrap();
+    assert_eq!(settings.value, "30");
+    // the value of a secret is restored encrypted as it was stored
+    let secret = db_get_settings_by_key(&restored_db, "db.password").unwrap().unwrap();
+    let original_secret = db_get_settings_by_key(&db, "db.password").unwrap().unwrap();
+    assert_eq!(secret.value, original_secret.value);
+    assert_eq!(restored_db.cipher.decrypt(&secret.value).unwrap(), "hunter2");
+    let trash = db_get_trash(&restored_db).unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].settings.key, "legacy");
+}
+
+#[test]
+fn test_backup_and_restore_large_values() {
+    // Arrange
+    let (_dir, db) = open_db();
+    // larger than the buffer the entries are copied with
+    let large_value = "x".repeat(100_000);
+    for i in 0..3 {
+        let settings = settings_of(&format!("large.{}", i), &large_value, SettingsValueType::Str);
+        db_create_settings(&db, &settings).unwrap().unwrap();
+    }
+
+    let mut archive = Vec::new();
+    db_write_backup(&db, &mut archive).unwrap();
+    let (_restored_dir, restored_db) = open_db();
+
+    // Act
+    let restored = db_restore_backup(&restored_db, archive.as_slice()).unwrap();
+
+    // Assert
+    assert_eq!(restored.partitions["settings"], 3);
+    for i in 0..3 {
+        let settings = db_get_settings_by_key(&restored_db, &format!("large.{}", i)).unwrap().unwrap();
+        assert_eq!(settings.value, large_value);
+    }
+}
+
+#[test]
+fn test_restore_into_not_empty_db() {
+    // Arrange
+    let (_dir, db) = open_db();
+    db_create_settings(&db, &settings_of("db.timeout", "30", SettingsValueType::Int)).unwrap().unwrap();
+    let mut archive = Vec::new();
+    db_write_backup(&db, &mut archive).unwrap();
+
+    // Act
+    let result = db_restore_backup(&db, archive.as_slice());
+
+    // Assert
+    assert!(matches!(result, Err(CustomError::ConflictError(_))));
+}
+
+#[test]
+fn test_restore_truncated_backup() {
+    // Arrange
+    let (_dir, db) = open_db();
+    db_create_settings(&db, &settings_of("db.timeout", "30", SettingsValueType::Int)).unwrap().unwrap();
+    let mut archive = Vec::new();
+    db_write_backup(&db, &mut archive).unwrap();
+    archive.truncate(archive.len() / 2);
+
+    let (_restored_dir, restored_db) = open_db();
+
+    // Act
+    let result = db_restore_backup(&restored_db, archive.as_slice());
+
+    // Assert
+    assert!(matches!(result, Err(CustomError::ValidationError(_))));
+    assert!(db_get_settings_by_key(&restored_db, "db.timeout").unwrap().is_none());
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::helpers::{open_db, settings_of};
+use dynamic_settings::enums::SettingsValueType;
+use dynamic_settings::models::{SettingsDBRow, UpdateSettingsRequest};
+use dynamic_settings::repository::{
+    db_approve_change_request, db_create_change_request, db_create_settings, db_get_settings_by_key
+};
+
+const CONCURRENT_APPROVALS: usize = 16;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_concurrent_approvals_apply_once() {
+    // Arrange
+    let (_dir, db) = open_db();
+    let db = Arc::new(db);
+
+    let settings = SettingsDBRow {
+        version: 1,
+        ..settings_of("prod.replicas", "3", SettingsValueType::Int)
+    };
+    db_create_settings(&db, &settings).unwrap().unwrap();
+
+    let update = UpdateSettingsRequest {
+        key: "prod.replicas".to_string(),
+        value: Some("5".to_string()),
+        ..Default::default()
+    };
+    let change_request = db_create_change_request(&db, "prod.replicas", Some(update), "requester")
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let tasks: Vec<_> = (0..CONCURRENT_APPROVALS)
+        .map(|i| {
+            let db = db.clone();
+            let id = change_request.id.clone();
+            tokio::task::spawn_blocking(move || {
+                db_approve_change_request(&db, &id, &format!("approver-{}", i))
+            })
+        })
+        .collect();
+
+    let mut approved = 0;
+    for task in tasks {
+        if task.await.unwrap().is_ok() {
+            approved += 1;
+        }
+    }
+
+    // Assert
+    assert_eq!(approved, 1);
+    let settings = db_get_settings_by_key(&db, "prod.replicas").unwrap().unwrap();
+    assert_eq!(settings.value, "5");
+    // the change is applied only once
+    assert_eq!(settings.version, 2);
+}
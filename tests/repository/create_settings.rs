@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::helpers::{open_db, settings_of};
+use dynamic_settings::enums::SettingsValueType;
+use dynamic_settings::models::SettingsDBRow;
+use dynamic_settings::repository::db_create_settings;
+
+const CONCURRENT_CREATES: usize = 32;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_concurrent_create_settings_only_one_created() {
+    // Arrange
+    let (_dir, db) = open_db();
+    let db = Arc::new(db);
+
+    // Act
+    let tasks: Vec<_> = (0..CONCURRENT_CREATES)
+        .map(|i| {
+            let db = db.clone();
+            tokio::task::spawn_blocking(move || {
+                let settings = settings_of("shard_map", &i.to_string(), SettingsValueType::Int);
+                db_create_settings(&db, &settings).map(|key| key.map(|_| i))
+            })
+        })
+        .collect();
+
+    let mut created = Vec::new();
+    let mut conflicts = 0;
+    for task in tasks {
+        match task.await.unwrap().unwrap() {
+            Some(i) => created.push(i),
+            None => conflicts += 1,
+        }
+    }
+
+    // Assert
+    assert_eq!(created.len(), 1);
+    assert_eq!(conflicts, CONCURRENT_CREATES - 1);
+
+    // the stored value is the one of the only successful create
+    let item = db.partition.get("shard_map").unwrap().unwrap();
+    let settings = SettingsDBRow::decode(&item).unwrap();
+    assert_eq!(settings.value, created[0].to_string());
+}
@@ -0,0 +1,100 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::helpers::{open_db, settings_of};
+use dynamic_settings::enums::{ExpireAction, SettingsValueType};
+use dynamic_settings::models::{SettingsDBRow, UpdateSettingsRequest};
+use dynamic_settings::repository::{
+    db_create_settings, db_expire_settings, db_get_settings_by_key, db_index_expirations, db_update_settings_by_key
+};
+
+fn override_until(value: &str, expires_at: DateTime<Utc>) -> UpdateSettingsRequest {
+    UpdateSettingsRequest {
+        key: "rate_limit".to_string(),
+        value: Some(value.to_string()),
+        expires_at: Some(expires_at),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_expire_created_settings_deleted() {
+    // Arrange
+    let (_dir, db) = open_db();
+    let expires_at = Utc::now() + Duration::minutes(1);
+    let settings = SettingsDBRow {
+        expires_at: Some(expires_at),
+        ..settings_of("rate_limit", "100", SettingsValueType::Int)
+    };
+    db_create_settings(&db, &settings).unwrap().unwrap();
+    assert_eq!(db_expire_settings(&db, Utc::now()).unwrap(), 0);
+
+    // Act
+    let expired = db_expire_settings(&db, expires_at).unwrap();
+
+    // Assert
+    assert_eq!(expired, 1);
+    assert!(db_get_settings_by_key(&db, "rate_limit").unwrap().is_none());
+}
+
+#[test]
+fn test_expire_overridden_settings_deleted_on_expire() {
+    // Arrange
+    let (_dir, db) = open_db();
+    let expires_at = Utc::now() + Duration::minutes(1);
+    db_create_settings(&db, &settings_of("rate_limit", "100", SettingsValueType::Int)).unwrap().unwrap();
+    db_update_settings_by_key(&db, &UpdateSettingsRequest {
+        on_expire: Some(ExpireAction::Delete),
+        ..override_until("200", expires_at)
+    }).unwrap().unwrap();
+
+    // Act
+    let expired = db_expire_settings(&db, expires_at).unwrap();
+
+    // Assert
+    assert_eq!(expired, 1);
+    assert!(db_get_settings_by_key(&db, "rate_limit").unwrap().is_none());
+}
+
+#[test]
+fn test_expire_settings_overridden_again() {
+    // Arrange
+    let (_dir, db) = open_db();
+    let now = Utc::now();
+    db_create_settings(&db, &settings_of("rate_limit", "100", SettingsValueType::Int)).unwrap().unwrap();
+    db_update_settings_by_key(&db, &override_until("200", now + Duration::minutes(1))).unwrap().unwrap();
+    db_update_settings_by_key(&db, &override_until("300", now + Duration::minutes(2))).unwrap().unwrap();
+
+    // Act
+    let expired_at_first_expiry = db_expire_settings(&db, now + Duration::minutes(1)).unwrap();
+    let expired_at_second_expiry = db_expire_settings(&db, now + Duration::minutes(2)).unwrap();
+
+    // Assert
+    // the entry of the first expiry is dropped without touching the settings
+    assert_eq!(expired_at_first_expiry, 0);
+    assert_eq!(expired_at_second_expiry, 1);
+    let settings = db_get_settings_by_key(&db, "rate_limit").unwrap().unwrap();
+    assert_eq!(settings.value, "100");
+    assert!(settings.expires_at.is_none());
+    assert!(settings.revert_to.is_none());
+}
+
+#[test]
+fn test_expire_settings_written_before_the_index() {
+    // Arrange
+    let (_dir, db) = open_db();
+    let expires_at = Utc::now() - Duration::minutes(1);
+    let settings = SettingsDBRow {
+        expires_at: Some(expires_at),
+        ..settings_of("rate_limit", "100", SettingsValueType::Int)
+    };
+    db.partition.insert("rate_limit", settings.encode()).unwrap();
+    assert_eq!(db_expire_settings(&db, Utc::now()).unwrap(), 0);
+
+    // Act
+    let indexed = db_index_expirations(&db).unwrap();
+
+    // Assert
+    assert_eq!(indexed, 1);
+    assert_eq!(db_expire_settings(&db, Utc::now()).unwrap(), 1);
+    assert!(db_get_settings_by_key(&db, "rate_limit").unwrap().is_none());
+}
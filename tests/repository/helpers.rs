@@ -0,0 +1,30 @@
+use chrono::Utc;
+use fjall::Config;
+
+use dynamic_settings::enums::SettingsValueType;
+use dynamic_settings::models::{SettingsDB, SettingsDBRow};
+use dynamic_settings::utils::SecretCipher;
+
+/// Opens an empty database in a temporary directory, which is removed when the returned `TempDir` is dropped
+pub fn open_db() -> (tempfile::TempDir, SettingsDB) {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let keyspace = Config::new(dir.path())
+        .open_transactional()
+        .expect("Failed connect to keyspace");
+    let db = SettingsDB::open(keyspace, SecretCipher::new("secret-key"))
+        .expect("Failed to connect to partition");
+    (dir, db)
+}
+
+/// Settings with the given value created and updated now, the other fields are set with the struct update syntax
+pub fn settings_of(key: &str, value: &str, value_type: SettingsValueType) -> SettingsDBRow {
+    let now = Utc::now();
+    SettingsDBRow {
+        key: key.to_string(),
+        value: value.to_string(),
+        value_type,
+        created_at: now,
+        updated_at: now,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,8 @@
+mod helpers;
+mod backup;
+mod change_requests;
+mod create_settings;
+mod expire_settings;
+mod schedules;
+mod settings_format;
+mod trash;
@@ -0,0 +1,80 @@
+use chrono::{Duration, Utc};
+
+use crate::helpers::{open_db, settings_of};
+use dynamic_settings::enums::SettingsValueType;
+use dynamic_settings::models::ScheduledChangeDBRow;
+use dynamic_settings::repository::{
+    db_apply_due_scheduled_changes, db_create_scheduled_change, db_create_settings, db_delete_scheduled_change,
+    db_get_scheduled_changes, db_get_settings_by_key
+};
+
+#[test]
+fn test_scheduled_change_applied_when_due() {
+    // Arrange
+    let (_dir, db) = open_db();
+    let apply_at = Utc::now() + Duration::hours(1);
+    db_create_settings(&db, &settings_of("replicas", "1", SettingsValueType::Int)).unwrap().unwrap();
+    db_create_scheduled_change(&db, "replicas", "5", apply_at).unwrap().unwrap();
+    // not applied before `applyAt`
+    assert_eq!(db_apply_due_scheduled_changes(&db, Utc::now(), |_| false).unwrap(), 0);
+    assert_eq!(db_get_settings_by_key(&db, "replicas").unwrap().unwrap().value, "1");
+
+    // Act
+    let applied = db_apply_due_scheduled_changes(&db, apply_at, |_| false).unwrap();
+
+    // Assert
+    assert_eq!(applied, 1);
+    assert_eq!(db_get_settings_by_key(&db, "replicas").unwrap().unwrap().value, "5");
+    assert!(db_get_scheduled_changes(&db, None).unwrap().is_empty());
+}
+
+#[test]
+fn test_cancelled_scheduled_change_not_applied() {
+    // Arrange
+    let (_dir, db) = open_db();
+    let apply_at = Utc::now() + Duration::hours(1);
+    db_create_settings(&db, &settings_of("replicas", "1", SettingsValueType::Int)).unwrap().unwrap();
+    let scheduled = db_create_scheduled_change(&db, "replicas", "5", apply_at).unwrap().unwrap();
+    db_delete_scheduled_change(&db, "replicas", &scheduled.id).unwrap().unwrap();
+
+    // Act
+    let applied = db_apply_due_scheduled_changes(&db, apply_at, |_| false).unwrap();
+
+    // Assert
+    assert_eq!(applied, 0);
+    assert_eq!(db_get_settings_by_key(&db, "replicas").unwrap().unwrap().value, "1");
+}
+
+#[test]
+fn test_bad_scheduled_changes_do_not_stop_the_others() {
+    // Arrange
+    let (_dir, db) = open_db();
+
+    let now = Utc::now();
+    for (key, value_type) in [("replicas", SettingsValueType::Int), ("token", SettingsValueType::Secret)] {
+        db_create_settings(&db, &settings_of(key, "1", value_type)).unwrap().unwrap();
+    }
+
+    // written by a newer version, can not be read
+    db.schedules.insert("corrupt", b"\xc1").unwrap();
+    // encrypted with another secret key
+    let undecryptable = ScheduledChangeDBRow {
+        id: "undecryptable".to_string(),
+        key: "token".to_string(),
+        value: "not encrypted".to_string(),
+        secret: true,
+        apply_at: now,
+        created_at: now,
+    };
+    let serialized: Vec<u8> = (&undecryptable).into();
+    db.schedules.insert(&undecryptable.id, serialized).unwrap();
+    db_create_scheduled_change(&db, "replicas", "5", now).unwrap().unwrap();
+
+    // Act
+    let applied = db_apply_due_scheduled_changes(&db, now + Duration::seconds(1), |_| false).unwrap();
+
+    // Assert
+    assert_eq!(applied, 1);
+    assert_eq!(db_get_settings_by_key(&db, "replicas").unwrap().unwrap().value, "5");
+    assert!(db_get_scheduled_changes(&db, None).unwrap().is_empty());
+}
@@ -0,0 +1,198 @@
+use chrono::{DateTime, Utc};
+
+use crate::helpers::open_db;
+use dynamic_settings::enums::{ChangeRequestKind, ChangeRequestStatus, SettingsValueType};
+use dynamic_settings::models::{
+    settings_format_version, ChangeRequestDBRow, SettingsDBRow, SettingsRevert,
+    TrashedSettingsDBRow, UpdateSettingsRequest, CHANGE_REQUEST_FORMAT_VERSION, SETTINGS_FORMAT_VERSION
+};
+use dynamic_settings::repository::{db_get_settings_by_key, db_migrate_settings};
+
+/// Written by the current format, a change that breaks it needs a new format version and a migration
+const SETTINGS_V1: &[u8] = include_bytes!("fixtures/settings_v1.bin");
+/// Written before the format byte, with only the fields of the first release
+const SETTINGS_V0: &[u8] = include_bytes!("fixtures/settings_v0.bin");
+
+fn time(value: &str) -> DateTime<Utc> {
+    value.parse().unwrap()
+}
+
+fn fixture_settings() -> SettingsDBRow {
+    SettingsDBRow {
+        key: "http.timeout".to_string(),
+        value: "30".to_string(),
+        value_type: SettingsValueType::Int,
+        created_at: time("2024-05-01T10:00:00Z"),
+        updated_at: time("2024-05-02T12:30:00Z"),
+        expires_at: Some(time("2030-01-01T00:00:00Z")),
+        revert_to: Some(SettingsRevert {
+            value: "10".to_string(),
+            value_type: SettingsValueType::Int,
+        }),
+        locked: true,
+        description: Some("Timeout of outgoing requests".to_string()),
+        owner: Some("team-core".to_string()),
+        tags: vec!["network".to_string(), "timeouts".to_string()],
+        version: 3,
+    }
+}
+
+#[test]
+fn test_decode_current_format_fixture() {
+    // Act
+    let settings = SettingsDBRow::decode(SETTINGS_V1).unwrap();
+
+    // Assert
+    let expected = fixture_settings();
+    assert_eq!(settings_format_version(SETTINGS_V1).unwrap(), 1);
+    assert_eq!(settings.key, expected.key);
+    assert_eq!(settings.value, expected.value);
+    assert_eq!(settings.value_type, expected.value_type);
+    assert_eq!(settings.created_at, expected.created_at);
+    assert_eq!(settings.updated_at, expected.updated_at);
+    assert_eq!(settings.expires_at, expected.expires_at);
+    let revert_to = settings.revert_to.unwrap();
+    assert_eq!(revert_to.value, "10");
+    assert_eq!(revert_to.value_type, SettingsValueType::Int);
+    assert!(settings.locked);
+    assert_eq!(settings.description, expected.description);
+    assert_eq!(settings.owner, expected.owner);
+    assert_eq!(settings.tags, expected.tags);
+    assert_eq!(settings.version, expected.version);
+}
+
+#[test]
+fn test_encode_matches_current_format_fixture() {
+    // Act
+    let bytes = fixture_settings().encode();
+
+    // Assert
+    assert_eq!(bytes, SETTINGS_V1);
+}
+
+#[test]
+fn test_decode_unversioned_fixture() {
+    // Act
+    let settings = SettingsDBRow::decode(SETTINGS_V0).unwrap();
+
+    // Assert
+    assert_eq!(settings_format_version(SETTINGS_V0).unwrap(), 0);
+    assert_eq!(settings.key, "http.retries");
+    assert_eq!(settings.value, "5");
+    assert_eq!(settings.value_type, SettingsValueType::Int);
+    assert_eq!(settings.created_at, time("2023-11-20T08:15:00Z"));
+    assert_eq!(settings.updated_at, time("2023-11-21T09:45:00Z"));
+    assert!(settings.expires_at.is_none());
+    assert!(!settings.locked);
+    assert!(settings.tags.is_empty());
+    assert_eq!(settings.version, 0);
+}
+
+#[test]
+fn test_decode_newer_format_fails() {
+    // Arrange
+    let mut bytes = SETTINGS_V1.to_vec();
+    bytes[0] = SETTINGS_FORMAT_VERSION + 1;
+
+    // Act
+    let result = SettingsDBRow::decode(&bytes);
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_migrate_settings_rewrites_old_rows() {
+    // Arrange
+    let (_dir, db) = open_db();
+    db.partition.insert("http.retries", SETTINGS_V0).unwrap();
+    db.partition.insert("http.timeout", SETTINGS_V1).unwrap();
+
+    // Act
+    let migrated = db_migrate_settings(&db).unwrap();
+
+    // Assert
+    assert_eq!(migrated, 1);
+    let stored = db.partition.get("http.retries").unwrap().unwrap();
+    assert_eq!(settings_format_version(&stored).unwrap(), SETTINGS_FORMAT_VERSION);
+    let settings = db_get_settings_by_key(&db, "http.retries").unwrap().unwrap();
+    assert_eq!(settings.value, "5");
+
+    // already migrated rows are left as they are
+    assert_eq!(db_migrate_settings(&db).unwrap(), 0);
+}
+
+#[test]
+fn test_decode_trashed_settings_in_old_settings_format() {
+    // Arrange
+    let deleted_at = time("2024-06-01T00:00:00Z");
+    let mut bytes = rmp_serde::to_vec(&deleted_at).unwrap();
+    bytes.extend(SETTINGS_V0);
+
+    // Act
+    let trashed = TrashedSettingsDBRow::decode(&bytes).unwrap();
+
+    // Assert
+    assert_eq!(trashed.deleted_at, deleted_at);
+    assert_eq!(trashed.settings.key, "http.retries");
+    assert_eq!(trashed.settings.value, "5");
+
+    // written back in the current settings format
+    let encoded = trashed.encode();
+    assert!(encoded.ends_with(&trashed.settings.encode()));
+    assert_eq!(TrashedSettingsDBRow::decode(&encoded).unwrap().settings.key, "http.retries");
+}
+
+#[test]
+fn test_decode_unversioned_trashed_settings() {
+    // Arrange
+    let deleted_at = time("2024-06-01T00:00:00Z");
+    let bytes = rmp_serde::to_vec(&(fixture_settings(), deleted_at)).unwrap();
+
+    // Act
+    let trashed = TrashedSettingsDBRow::decode(&bytes).unwrap();
+
+    // Assert
+    assert_eq!(trashed.deleted_at, deleted_at);
+    assert_eq!(trashed.settings.key, "http.timeout");
+    assert_eq!(trashed.settings.version, 3);
+}
+
+#[test]
+fn test_decode_change_request_formats() {
+    // Arrange
+    let change_request = ChangeRequestDBRow {
+        id: "1".to_string(),
+        key: "http.timeout".to_string(),
+        kind: ChangeRequestKind::Update,
+        update: Some(UpdateSettingsRequest {
+            key: "http.timeout".to_string(),
+            value: Some("60".to_string()),
+            ..Default::default()
+        }),
+        secret: false,
+        status: ChangeRequestStatus::Pending,
+        requested_by: "requester".to_string(),
+        resolved_by: None,
+        created_at: time("2024-06-01T00:00:00Z"),
+        resolved_at: None,
+    };
+    let unversioned = rmp_serde::to_vec(&change_request).unwrap();
+
+    // Act
+    let encoded = change_request.encode();
+    let decoded = ChangeRequestDBRow::decode(&encoded).unwrap();
+    let decoded_unversioned = ChangeRequestDBRow::decode(&unversioned).unwrap();
+
+    // Assert
+    assert_eq!(encoded[0], CHANGE_REQUEST_FORMAT_VERSION);
+    for decoded in [decoded, decoded_unversioned] {
+        assert_eq!(decoded.key, "http.timeout");
+        assert_eq!(decoded.update.unwrap().value.as_deref(), Some("60"));
+        assert_eq!(decoded.requested_by, "requester");
+    }
+
+    let mut newer = encoded.clone();
+    newer[0] = CHANGE_REQUEST_FORMAT_VERSION + 1;
+    assert!(ChangeRequestDBRow::decode(&newer).is_err());
+}
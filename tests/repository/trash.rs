@@ -0,0 +1,22 @@
+use chrono::{Duration, Utc};
+
+use crate::helpers::{open_db, settings_of};
+use dynamic_settings::enums::SettingsValueType;
+use dynamic_settings::repository::{db_create_settings, db_delete_settings_by_keys, db_get_trash, db_purge_trash};
+
+#[test]
+fn test_trash_purged_after_retention() {
+    // Arrange
+    let (_dir, db) = open_db();
+    db_create_settings(&db, &settings_of("rate_limit", "100", SettingsValueType::Int)).unwrap().unwrap();
+    db_delete_settings_by_keys(&db, vec!["rate_limit".to_string()], |_| false, "tests", false).unwrap();
+    // deleted after the cutoff, so it is kept
+    assert_eq!(db_purge_trash(&db, Utc::now() - Duration::hours(1)).unwrap(), 0);
+
+    // Act
+    let purged = db_purge_trash(&db, Utc::now() + Duration::seconds(1)).unwrap();
+
+    // Assert
+    assert_eq!(purged, 1);
+    assert!(db_get_trash(&db).unwrap().is_empty());
+}
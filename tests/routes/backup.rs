@@ -0,0 +1,59 @@
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+
+use crate::helpers::{spawn_app, make_request, TestApp};
+use dynamic_settings::models::BackupManifest;
+
+async fn download_backup(app: &TestApp, api_key: &str) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/admin/backup", &app.address),
+        api_key.to_string(),
+        None,
+        reqwest::Method::GET,
+    ).await
+}
+
+#[tokio::test]
+async fn test_download_backup() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = download_backup(app, &app.admin_api_key).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "application/gzip");
+    let archive = response.bytes().await.unwrap();
+
+    let mut archive = tar::Archive::new(GzDecoder::new(archive.as_ref()));
+    let mut names = Vec::new();
+    let mut manifest: Option<BackupManifest> = None;
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let name = entry.path().unwrap().to_string_lossy().into_owned();
+        if name == "manifest.json" {
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            manifest = Some(serde_json::from_slice(&data).unwrap());
+        }
+        names.push(name);
+    }
+    assert_eq!(names, vec![
+        "manifest.json", "settings.bin", "schedules.bin", "change_requests.bin", "trash.bin"
+    ]);
+    assert_eq!(manifest.unwrap().partitions.len(), 4);
+}
+
+#[tokio::test]
+async fn test_download_backup_requires_admin() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = download_backup(app, &app.api_key).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+}
@@ -0,0 +1,124 @@
+use uuid::Uuid;
+
+use crate::helpers::{create_settings, int_settings, get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{ChangeRequestDBRow, DeleteSettingsResponse};
+use dynamic_settings::enums::{ChangeRequestKind, ChangeRequestStatus};
+
+async fn resolve(app: &TestApp, id: &str, action: &str, api_key: &str) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/change-requests/{}/{}", &app.address, id, action),
+        api_key.to_string(),
+        None,
+        reqwest::Method::POST,
+    ).await
+}
+
+#[tokio::test]
+async fn test_update_protected_settings_approved() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = format!("protected.{}", Uuid::new_v4());
+    create_settings(&app.partition, &int_settings(&key));
+    let body = serde_json::json!({
+        "key": key,
+        "value": "200",
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::PUT,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 202);
+    let change_request: ChangeRequestDBRow = response.json().await.unwrap();
+    assert_eq!(change_request.kind, ChangeRequestKind::Update);
+    assert_eq!(change_request.status, ChangeRequestStatus::Pending);
+
+    // Not applied before the approval
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "100");
+
+    let pending: Vec<ChangeRequestDBRow> = make_request(
+        format!("{}/api/v1/change-requests?status=pending", &app.address),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await.json().await.unwrap();
+    assert!(pending.iter().any(|r| r.id == change_request.id));
+
+    // The requester can not approve its own change request
+    let response = resolve(app, &change_request.id, "approve", &app.api_key).await;
+    assert_eq!(response.status(), 403);
+
+    let response = resolve(app, &change_request.id, "approve", &app.admin_api_key).await;
+    assert_eq!(response.status(), 200);
+    let change_request: ChangeRequestDBRow = response.json().await.unwrap();
+    assert_eq!(change_request.status, ChangeRequestStatus::Approved);
+
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "200");
+
+    // Can be resolved only once
+    let response = resolve(app, &change_request.id, "reject", &app.admin_api_key).await;
+    assert_eq!(response.status(), 409);
+}
+
+#[tokio::test]
+async fn test_update_protected_settings_invalid_value() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = format!("protected.{}", Uuid::new_v4());
+    create_settings(&app.partition, &int_settings(&key));
+    let body = serde_json::json!({
+        "key": key,
+        "value": "not a number",
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::PUT,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn test_delete_protected_settings_rejected() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = format!("protected.{}", Uuid::new_v4());
+    create_settings(&app.partition, &int_settings(&key));
+    let body = serde_json::json!({
+        "keys": [key],
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 202);
+    let response: DeleteSettingsResponse = response.json().await.unwrap();
+    assert_eq!(response.change_requests.len(), 1);
+    let change_request = &response.change_requests[0];
+    assert_eq!(change_request.kind, ChangeRequestKind::Delete);
+
+    let response = resolve(app, &change_request.id, "reject", &app.admin_api_key).await;
+    assert_eq!(response.status(), 200);
+    let change_request: ChangeRequestDBRow = response.json().await.unwrap();
+    assert_eq!(change_request.status, ChangeRequestStatus::Rejected);
+
+    assert!(get_settings(&app.partition, &key).unwrap().is_some());
+}
@@ -1,6 +1,6 @@
 use uuid::Uuid;
 
-use chrono::Utc;
+use chrono::{Duration, Utc};
 
 use crate::helpers::{create_settings, get_settings, spawn_app, make_request};
 use dynamic_settings::models::MessageResponse;
@@ -97,6 +97,7 @@ async fn test_create_settings_key_already_exists() {
         value_type: SettingsValueType::Int,
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        ..Default::default()
     };
 
     create_settings(&app.partition, &settings);
@@ -182,3 +183,73 @@ async fn test_create_settings_invalid_input_key_is_to_big() {
     let body: MessageResponse = response.json().await.unwrap();
     assert_eq!(body.message, "Key length should be less than 1024 bytes");
 }
+
+#[tokio::test]
+async fn test_upsert_settings_creates_missing() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+
+    let body = serde_json::json!({
+        "key": key,
+        "value": "100",
+        "type": "int"
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings?upsert=true", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 201);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "100");
+}
+
+#[tokio::test]
+async fn test_upsert_settings_updates_existing() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    let created_at = Utc::now() - Duration::days(1);
+
+    let settings = SettingsDBRow {
+        key: key.clone(),
+        value: "100".to_string(),
+        value_type: SettingsValueType::Int,
+        created_at,
+        updated_at: created_at,
+        ..Default::default()
+    };
+    create_settings(&app.partition, &settings);
+
+    let body = serde_json::json!({
+        "key": key,
+        "value": "true",
+        "type": "bool"
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings?upsert=true", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, format!("Settings with key '{}' updated", key));
+
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "true");
+    assert_eq!(settings.value_type, SettingsValueType::Bool);
+    assert_eq!(settings.created_at, created_at);
+    assert!(settings.updated_at > created_at);
+}
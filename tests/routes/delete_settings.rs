@@ -2,9 +2,18 @@ use uuid::Uuid;
 
 use chrono::Utc;
 
-use crate::helpers::{create_settings, get_settings, spawn_app, make_request};
-use dynamic_settings::models::{SettingsDBRow};
-use dynamic_settings::enums::SettingsValueType;
+use crate::helpers::{create_settings, int_settings, get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{DeleteSettingsResponse, SettingsDBRow};
+use dynamic_settings::enums::{DeleteOutcome, SettingsValueType};
+
+async fn delete_by_prefix(app: &TestApp, prefix: &str, confirm: usize) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings?prefix={}&confirm={}", &app.address, prefix, confirm),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::DELETE,
+    ).await
+}
 
 #[tokio::test]
 async fn test_deleted_settings() {
@@ -20,6 +29,7 @@ async fn test_deleted_settings() {
         value_type: SettingsValueType::Int,
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        ..Default::default()
     };
 
     create_settings(&app.partition, &settings);
@@ -42,3 +52,246 @@ async fn test_deleted_settings() {
     let result = get_settings(&app.partition, &key);
     assert!(result.unwrap().is_none());
 }
+
+#[tokio::test]
+async fn test_delete_settings_reports_outcomes() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+    let missing_key = Uuid::new_v4().to_string();
+
+    let body = serde_json::json!({
+        "keys": [key, missing_key]
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let response: DeleteSettingsResponse = response.json().await.unwrap();
+    let outcomes: Vec<(String, DeleteOutcome)> = response.results
+        .into_iter()
+        .map(|result| (result.key, result.outcome))
+        .collect();
+    assert_eq!(outcomes, vec![
+        (key.clone(), DeleteOutcome::Deleted),
+        (missing_key, DeleteOutcome::NotFound),
+    ]);
+    assert!(response.change_requests.is_empty());
+
+    assert!(get_settings(&app.partition, &key).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_delete_settings_strict_with_missing_key() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let body = serde_json::json!({
+        "keys": [key, Uuid::new_v4().to_string()],
+        "strict": true
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 404);
+
+    // nothing is deleted
+    assert!(get_settings(&app.partition, &key).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_delete_settings_strict() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let body = serde_json::json!({
+        "keys": [key],
+        "strict": true
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert!(get_settings(&app.partition, &key).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_delete_settings_by_prefix() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = format!("{}.", Uuid::new_v4());
+    let first_key = format!("{}first", prefix);
+    create_settings(&app.partition, &int_settings(&first_key));
+    let second_key = format!("{}second", prefix);
+    create_settings(&app.partition, &int_settings(&second_key));
+    let other_key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&other_key));
+
+    // Act
+    let response = delete_by_prefix(app, &prefix, 2).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let response: DeleteSettingsResponse = response.json().await.unwrap();
+    let keys: Vec<String> = response.results.into_iter().map(|result| result.key).collect();
+    assert_eq!(keys, vec![first_key.clone(), second_key.clone()]);
+
+    assert!(get_settings(&app.partition, &first_key).unwrap().is_none());
+    assert!(get_settings(&app.partition, &second_key).unwrap().is_none());
+    assert!(get_settings(&app.partition, &other_key).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_delete_settings_by_prefix_wrong_confirm() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = format!("{}.", Uuid::new_v4());
+    let key = format!("{}first", prefix);
+    create_settings(&app.partition, &int_settings(&key));
+    create_settings(&app.partition, &int_settings(&format!("{}second", prefix)));
+
+    // Act
+    let response = delete_by_prefix(app, &prefix, 1).await;
+
+    // Assert
+    assert_eq!(response.status(), 409);
+    assert!(get_settings(&app.partition, &key).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_delete_settings_by_prefix_with_locked() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = format!("{}.", Uuid::new_v4());
+    let key = format!("{}first", prefix);
+    create_settings(&app.partition, &int_settings(&key));
+    create_settings(&app.partition, &SettingsDBRow { locked: true, ..int_settings(&format!("{}second", prefix)) });
+
+    // Act
+    let response = delete_by_prefix(app, &prefix, 2).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+    assert!(get_settings(&app.partition, &key).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_delete_settings_by_protected_prefix() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = format!("protected.{}", Uuid::new_v4());
+    create_settings(&app.partition, &int_settings(&key));
+
+    // Act
+    let response = delete_by_prefix(app, "protected.", 1).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+    assert!(get_settings(&app.partition, &key).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_delete_settings_by_empty_prefix() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = delete_by_prefix(app, "", 0).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn test_delete_settings_with_protected_keys() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+    let protected_key = format!("protected.{}", Uuid::new_v4());
+    create_settings(&app.partition, &int_settings(&protected_key));
+    let locked_protected_key = format!("protected.{}", Uuid::new_v4());
+    create_settings(&app.partition, &SettingsDBRow { locked: true, ..int_settings(&locked_protected_key) });
+
+    let body = serde_json::json!({
+        "keys": [key, protected_key, locked_protected_key]
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 202);
+    let response: DeleteSettingsResponse = response.json().await.unwrap();
+    let outcomes: Vec<(String, DeleteOutcome)> = response.results
+        .into_iter()
+        .map(|result| (result.key, result.outcome))
+        .collect();
+    assert_eq!(outcomes, vec![
+        (key.clone(), DeleteOutcome::Deleted),
+        (protected_key.clone(), DeleteOutcome::PendingApproval),
+        (locked_protected_key.clone(), DeleteOutcome::Locked),
+    ]);
+    assert_eq!(response.change_requests.len(), 1);
+    assert_eq!(response.change_requests[0].key, protected_key);
+
+    assert!(get_settings(&app.partition, &key).unwrap().is_none());
+    assert!(get_settings(&app.partition, &protected_key).unwrap().is_some());
+    assert!(get_settings(&app.partition, &locked_protected_key).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_delete_settings_strict_with_missing_protected_key() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let body = serde_json::json!({
+        "keys": [key, format!("protected.{}", Uuid::new_v4())],
+        "strict": true
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 404);
+    assert!(get_settings(&app.partition, &key).unwrap().is_some());
+}
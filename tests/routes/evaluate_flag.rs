@@ -0,0 +1,310 @@
+use uuid::Uuid;
+
+use crate::helpers::{create_settings, int_settings, settings_of, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{FlagEvaluationResponse, FlagsEvaluationResponse, MessageResponse};
+use dynamic_settings::enums::{FlagEvaluationReason, SettingsValueType};
+
+async fn evaluate(app: &TestApp, key: &str, subject: &str) -> reqwest::Response {
+    evaluate_with_context(app, key, subject, serde_json::json!({})).await
+}
+
+async fn evaluate_with_context(
+    app: &TestApp,
+    key: &str,
+    subject: &str,
+    context: serde_json::Value,
+) -> reqwest::Response {
+    let body = serde_json::json!({
+        "subject": subject,
+        "context": context,
+    });
+    make_request(
+        format!("{}/api/v1/flags/{}/evaluate", &app.address, key),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await
+}
+
+#[tokio::test]
+async fn test_evaluate_flag_fully_on_and_off() {
+    // Arrange
+    let app = spawn_app().await;
+    let on_key = Uuid::new_v4().to_string();
+    let off_key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&on_key, "{\"rollout\": 100}", SettingsValueType::Flag));
+    create_settings(&app.partition, &settings_of(&off_key, "{\"rollout\": 0}", SettingsValueType::Flag));
+
+    for subject in ["user-1", "user-2", "user-3"] {
+        // Act
+        let on: FlagEvaluationResponse = evaluate(app, &on_key, subject).await.json().await.unwrap();
+        let off: FlagEvaluationResponse = evaluate(app, &off_key, subject).await.json().await.unwrap();
+
+        // Assert
+        assert!(on.evaluation.enabled);
+        assert!(!off.evaluation.enabled);
+    }
+}
+
+#[tokio::test]
+async fn test_evaluate_flag_rollout_is_deterministic() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, "{\"rollout\": 50}", SettingsValueType::Flag));
+
+    let mut enabled_count = 0;
+    for i in 0..100 {
+        let subject = format!("user-{}", i);
+
+        // Act
+        let first: FlagEvaluationResponse = evaluate(app, &key, &subject).await.json().await.unwrap();
+        let second: FlagEvaluationResponse = evaluate(app, &key, &subject).await.json().await.unwrap();
+
+        // Assert
+        assert_eq!(first.evaluation.enabled, second.evaluation.enabled);
+        if first.evaluation.enabled {
+            enabled_count += 1;
+        }
+    }
+    assert!((25..75).contains(&enabled_count));
+}
+
+#[tokio::test]
+async fn test_evaluate_flag_rules() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    let definition = serde_json::json!({
+        "rollout": 0,
+        "rules": [
+            {"attribute": "tenant", "operator": "equals", "values": ["blocked"], "result": false},
+            {"attribute": "country", "operator": "in", "values": ["NL", "DE"], "result": true},
+            {"attribute": "app.version", "operator": "semver-gte", "values": ["2.0.0"], "result": true},
+            {"attribute": "tenant", "operator": "regex", "values": ["^beta-"], "result": true},
+        ]
+    });
+    create_settings(&app.partition, &settings_of(&key, &definition.to_string(), SettingsValueType::Flag));
+
+    // TODO: use some kind of `parameterized` here
+    let test_cases = vec![
+        (serde_json::json!({"country": "NL"}), true, Some(1)),
+        (serde_json::json!({"app": {"version": "2.1.0"}}), true, Some(2)),
+        (serde_json::json!({"app": {"version": "1.9.0"}}), false, None),
+        (serde_json::json!({"tenant": "beta-acme"}), true, Some(3)),
+        // first matching rule wins
+        (serde_json::json!({"tenant": "blocked", "country": "NL"}), false, Some(0)),
+        (serde_json::json!({}), false, None),
+    ];
+
+    for (context, enabled, rule_index) in test_cases {
+        // Act
+        let response = evaluate_with_context(app, &key, "user-1", context).await;
+
+        // Assert
+        assert_eq!(response.status(), 200);
+
+        let body: FlagEvaluationResponse = response.json().await.unwrap();
+        assert_eq!(body.evaluation.enabled, enabled);
+        assert_eq!(body.evaluation.rule_index, rule_index);
+        let reason = match rule_index {
+            Some(_) => FlagEvaluationReason::Rule,
+            None => FlagEvaluationReason::Rollout,
+        };
+        assert_eq!(body.evaluation.reason, reason);
+    }
+}
+
+#[tokio::test]
+async fn test_create_flag_invalid_rule() {
+    // Arrange
+    let app = spawn_app().await;
+    let definition = serde_json::json!({
+        "rollout": 0,
+        "rules": [
+            {"attribute": "tenant", "operator": "regex", "values": ["("], "result": true},
+        ]
+    });
+    let body = serde_json::json!({
+        "key": Uuid::new_v4().to_string(),
+        "value": definition.to_string(),
+        "type": "flag"
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, "Rule for attribute 'tenant' has invalid regex '('");
+}
+
+#[tokio::test]
+async fn test_create_flag_too_large_regex() {
+    // Arrange
+    let app = spawn_app().await;
+    let pattern = r"(\w{100}){100}";
+    let definition = serde_json::json!({
+        "rollout": 0,
+        "rules": [
+            {"attribute": "tenant", "operator": "regex", "values": [pattern], "result": true},
+        ]
+    });
+    let body = serde_json::json!({
+        "key": Uuid::new_v4().to_string(),
+        "value": definition.to_string(),
+        "type": "flag"
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, format!("Rule for attribute 'tenant' has invalid regex '{}'", pattern));
+}
+
+#[tokio::test]
+async fn test_evaluate_flag_not_a_flag() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, "true", SettingsValueType::Bool));
+
+    // Act
+    let response = evaluate(app, &key, "user-1").await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, format!("Settings with key '{}' is not a flag", key));
+}
+
+#[tokio::test]
+async fn test_create_flag_invalid_rollout() {
+    // Arrange
+    let app = spawn_app().await;
+    let body = serde_json::json!({
+        "key": Uuid::new_v4().to_string(),
+        "value": "{\"rollout\": 150}",
+        "type": "flag"
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, "Flag rollout should be between 0 and 100");
+}
+
+#[tokio::test]
+async fn test_evaluate_flags_by_prefix_with_variants() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = format!("{}.", Uuid::new_v4());
+
+    let multivariate = serde_json::json!({
+        "rollout": 100,
+        "variants": [
+            {"name": "control", "payload": {"color": "blue"}, "weight": 50},
+            {"name": "treatment", "payload": {"color": "green"}, "weight": 50},
+        ]
+    });
+    let checkout_key = format!("{}checkout", prefix);
+    create_settings(&app.partition, &settings_of(&checkout_key, &multivariate.to_string(), SettingsValueType::Flag));
+    let search_key = format!("{}search", prefix);
+    create_settings(&app.partition, &settings_of(&search_key, "{\"rollout\": 100}", SettingsValueType::Flag));
+    create_settings(&app.partition, &int_settings(&format!("{}timeout", prefix)));
+
+    let body = serde_json::json!({
+        "prefix": prefix,
+        "subject": "user-1",
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/flags/evaluate", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+
+    let body: FlagsEvaluationResponse = response.json().await.unwrap();
+    assert!(body.corrupt_keys.is_empty());
+    let body = body.items;
+    // Only flags are evaluated
+    assert_eq!(body.len(), 2);
+
+    let checkout = &body[0];
+    assert_eq!(checkout.key, format!("{}checkout", prefix));
+    assert!(checkout.evaluation.enabled);
+    let expected_payload = match checkout.evaluation.variant.as_deref() {
+        Some("control") => serde_json::json!({"color": "blue"}),
+        Some("treatment") => serde_json::json!({"color": "green"}),
+        variant => panic!("Unexpected variant {:?}", variant),
+    };
+    assert_eq!(checkout.evaluation.payload, Some(expected_payload));
+
+    let search = &body[1];
+    assert_eq!(search.key, format!("{}search", prefix));
+    assert!(search.evaluation.enabled);
+    assert_eq!(search.evaluation.variant, None);
+}
+
+#[tokio::test]
+async fn test_create_flag_invalid_variant_weights() {
+    // Arrange
+    let app = spawn_app().await;
+    let definition = serde_json::json!({
+        "rollout": 100,
+        "variants": [
+            {"name": "control", "weight": 50},
+            {"name": "treatment", "weight": 40},
+        ]
+    });
+    let body = serde_json::json!({
+        "key": Uuid::new_v4().to_string(),
+        "value": definition.to_string(),
+        "type": "flag"
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, "Variant weights should sum up to 100, got 90");
+}
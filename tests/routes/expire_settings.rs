@@ -0,0 +1,136 @@
+use uuid::Uuid;
+
+use chrono::{Duration, Utc};
+
+use crate::helpers::{create_settings, int_settings, get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{MessageResponse, SettingsDBRow};
+
+async fn update(app: &TestApp, body: serde_json::Value) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::PUT,
+    ).await
+}
+
+#[tokio::test]
+async fn test_create_settings_with_ttl_deleted() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    let body = serde_json::json!({
+        "key": key,
+        "value": "100",
+        "type": "int",
+        "ttl": 60,
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 201);
+
+    let response = make_request(
+        format!("{}/api/v1/settings/{}", &app.address, key),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+    let settings: SettingsDBRow = response.json().await.unwrap();
+    let expires_at = settings.expires_at.unwrap();
+    assert!(expires_at > Utc::now() + Duration::seconds(50));
+    assert!(settings.revert_to.is_none());
+}
+
+#[tokio::test]
+async fn test_update_settings_with_ttl_reverted() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let body = serde_json::json!({
+        "key": key,
+        "value": "200",
+        "ttl": 60,
+    });
+
+    // Act
+    let response = update(app, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "200");
+    assert_eq!(settings.revert_to.unwrap().value, "100");
+
+    // Override on top of the override still reverts to the original value
+    let body = serde_json::json!({
+        "key": key,
+        "value": "300",
+        "ttl": 60,
+    });
+    assert_eq!(update(app, body).await.status(), 200);
+
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "300");
+    assert_eq!(settings.revert_to.unwrap().value, "100");
+}
+
+#[tokio::test]
+async fn test_update_settings_with_expires_at_deleted() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let expires_at = Utc::now() + Duration::hours(1);
+    let body = serde_json::json!({
+        "key": key,
+        "value": "200",
+        "expiresAt": expires_at,
+        "onExpire": "delete",
+    });
+
+    // Act
+    let response = update(app, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.expires_at, Some(expires_at));
+    assert!(settings.revert_to.is_none());
+}
+
+#[tokio::test]
+async fn test_update_settings_with_ttl_and_expires_at() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let body = serde_json::json!({
+        "key": key,
+        "value": "200",
+        "ttl": 60,
+        "expiresAt": Utc::now() + Duration::hours(1),
+    });
+
+    // Act
+    let response = update(app, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, "Only one of `ttl` and `expiresAt` should be provided");
+}
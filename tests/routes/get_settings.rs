@@ -0,0 +1,133 @@
+use uuid::Uuid;
+
+use chrono::{Duration, Utc};
+
+use crate::helpers::{create_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{SettingsDBRow, SettingsPageResponse};
+use dynamic_settings::enums::SettingsValueType;
+
+/// Creates `{prefix}.0` .. `{prefix}.{count - 1}`, the later the key the earlier it was updated
+fn create_many(app: &TestApp, prefix: &str, count: i64) {
+    let now = Utc::now();
+    for i in 0..count {
+        let settings = SettingsDBRow {
+            key: format!("{}.{}", prefix, i),
+            value: i.to_string(),
+            value_type: SettingsValueType::Int,
+            created_at: now,
+            updated_at: now - Duration::minutes(i),
+            ..Default::default()
+        };
+        create_settings(&app.partition, &settings);
+    }
+}
+
+async fn get_page(app: &TestApp, query: &str) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings?{}", &app.address, query),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await
+}
+
+async fn get_all_pages(app: &TestApp, query: &str) -> Vec<Vec<String>> {
+    let mut pages = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let query = match &cursor {
+            Some(cursor) => format!("{}&cursor={}", query, cursor),
+            None => query.to_string(),
+        };
+        let response = get_page(app, &query).await;
+        assert_eq!(response.status(), 200);
+
+        let page: SettingsPageResponse = response.json().await.unwrap();
+        pages.push(page.items.into_iter().map(|s| s.key).collect());
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            return pages;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_settings_paginated_by_key() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = Uuid::new_v4().to_string();
+    create_many(app, &prefix, 5);
+
+    // Act
+    let pages = get_all_pages(app, &format!("prefix={}&limit=2", prefix)).await;
+
+    // Assert
+    assert_eq!(pages, vec![
+        vec![format!("{}.0", prefix), format!("{}.1", prefix)],
+        vec![format!("{}.2", prefix), format!("{}.3", prefix)],
+        vec![format!("{}.4", prefix)],
+    ]);
+}
+
+#[tokio::test]
+async fn test_get_settings_paginated_by_updated_at() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = Uuid::new_v4().to_string();
+    create_many(app, &prefix, 3);
+
+    // Act
+    let pages = get_all_pages(app, &format!("prefix={}&limit=2&sort=updatedAt", prefix)).await;
+
+    // Assert
+    assert_eq!(pages, vec![
+        vec![format!("{}.0", prefix), format!("{}.1", prefix)],
+        vec![format!("{}.2", prefix)],
+    ]);
+}
+
+#[tokio::test]
+async fn test_get_settings_without_limit_returns_plain_array() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = Uuid::new_v4().to_string();
+    create_many(app, &prefix, 3);
+
+    // Act
+    let response = get_page(app, &format!("prefix={}&sort=updatedAt", prefix)).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let settings: Vec<SettingsDBRow> = response.json().await.unwrap();
+    let keys: Vec<String> = settings.into_iter().map(|s| s.key).collect();
+    assert_eq!(keys, vec![format!("{}.0", prefix), format!("{}.1", prefix), format!("{}.2", prefix)]);
+}
+
+#[tokio::test]
+async fn test_get_settings_invalid_cursor() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = get_page(app, "cursor=not-a-cursor").await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn test_get_settings_cursor_of_other_sort() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = Uuid::new_v4().to_string();
+    create_many(app, &prefix, 3);
+    let response = get_page(app, &format!("prefix={}&limit=2", prefix)).await;
+    let page: SettingsPageResponse = response.json().await.unwrap();
+    let cursor = page.next_cursor.unwrap();
+
+    // Act
+    let response = get_page(app, &format!("prefix={}&limit=2&sort=updatedAt&cursor={}", prefix, cursor)).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
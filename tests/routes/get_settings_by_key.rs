@@ -3,7 +3,7 @@ use uuid::Uuid;
 use chrono::Utc;
 
 use crate::helpers::{create_settings, spawn_app, make_request};
-use dynamic_settings::models::{MessageResponse, SettingsDBRow};
+use dynamic_settings::models::{GetSettingsByKeysResponse, MessageResponse, SettingsDBRow};
 use dynamic_settings::enums::SettingsValueType;
 
 #[tokio::test]
@@ -19,7 +19,8 @@ async fn test_get_settings_by_key_ok() {
         value: value.clone(),
         value_type: SettingsValueType::Int,
         created_at: Utc::now(),
-        updated_at: Utc::now()
+        updated_at: Utc::now(),
+        ..Default::default()
     };
 
     create_settings(&app.partition, &settings);
@@ -63,3 +64,63 @@ async fn test_get_settings_by_key_key_does_not_exist() {
         format!("Settings with key '{}' not found", key)
     );
 }
+
+#[tokio::test]
+async fn test_get_settings_by_keys() {
+    // Arrange
+    let app = spawn_app().await;
+
+    let key = Uuid::new_v4().to_string();
+    let missing_key = Uuid::new_v4().to_string();
+    let settings = SettingsDBRow {
+        key: key.clone(),
+        value: "100".to_string(),
+        value_type: SettingsValueType::Int,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        ..Default::default()
+    };
+    create_settings(&app.partition, &settings);
+
+    let body = serde_json::json!({
+        "keys": [key, missing_key, key]
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/batch-get", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+
+    let response: GetSettingsByKeysResponse = response.json().await.unwrap();
+    assert_eq!(response.found.len(), 1);
+    assert_eq!(response.found[0].key, key);
+    assert_eq!(response.found[0].value, "100");
+    assert_eq!(response.missing, vec![missing_key]);
+}
+
+#[tokio::test]
+async fn test_get_settings_by_keys_empty() {
+    // Arrange
+    let app = spawn_app().await;
+
+    let body = serde_json::json!({
+        "keys": []
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/batch-get", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
@@ -1,46 +1,52 @@
 use std::option::Option;
 
+use chrono::Utc;
+
 use dynamic_settings::config::get_config;
+use dynamic_settings::enums::SettingsValueType;
 use dynamic_settings::models::{SettingsDBRow, SettingsDB};
 use dynamic_settings::startup;
+use dynamic_settings::utils::SecretCipher;
 use fjall::TxPartitionHandle;
-use fjall::{Config, PartitionCreateOptions};
+use fjall::Config;
 use std::net::TcpListener;
 use reqwest::header::{HeaderMap, HeaderValue};
 use tokio::sync::OnceCell;
 
 pub struct TestApp {
     pub address: String,
+    pub db: SettingsDB,
     pub partition: TxPartitionHandle,
     pub api_key: String,
+    pub admin_api_key: String,
 }
 
 async fn setup_app() -> TestApp {
     let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind random port");
     let port = listener.local_addr().unwrap().port();
 
-    let config = get_config().expect("Failed to read configuration.");
+    std::env::set_var("ADMIN_API_KEY", "admin-api-key");
+    std::env::set_var("SECRET_KEY", "secret-key");
+    let mut config = get_config().expect("Failed to read configuration.");
+    config.protected_prefixes = "protected.".to_string();
 
     let keyspace = Config::new("db")
         .open_transactional()
         .expect("Failed connect to keyspace");
-    let partition = keyspace
-        .open_partition("settings", PartitionCreateOptions::default())
+    let settings_db = SettingsDB::open(keyspace, SecretCipher::new(&config.secret_key))
         .expect("Failed to connect to partition");
-    let settings_db = SettingsDB {
-        keyspace,
-        partition: partition.clone(),
-    };
 
-    let server = startup::run(listener, settings_db, config.api_key.clone())
+    let server = startup::run(listener, settings_db.clone(), config.clone())
         .expect("Failed to bind address");
 
     tokio::spawn(server);
 
     TestApp {
         address: format!("http://127.0.0.1:{}", port),
-        partition,
+        partition: settings_db.partition.clone(),
+        db: settings_db,
         api_key: config.api_key,
+        admin_api_key: config.admin_api_key,
     }
 }
 
@@ -90,6 +96,25 @@ pub fn create_settings(partition: &TxPartitionHandle, settings: &SettingsDBRow)
         .expect("Failed to insert settings");
 }
 
+/// Settings with the given value created and updated now, the other fields are set with the struct update syntax,
+/// e.g. `SettingsDBRow { locked: true, ..settings_of(&key, "100", SettingsValueType::Int) }`
+pub fn settings_of(key: &str, value: &str, value_type: SettingsValueType) -> SettingsDBRow {
+    let now = Utc::now();
+    SettingsDBRow {
+        key: key.to_string(),
+        value: value.to_string(),
+        value_type,
+        created_at: now,
+        updated_at: now,
+        ..Default::default()
+    }
+}
+
+/// `int` settings with value `100`
+pub fn int_settings(key: &str) -> SettingsDBRow {
+    settings_of(key, "100", SettingsValueType::Int)
+}
+
 pub fn get_settings(
     partition: &TxPartitionHandle,
     key: &str,
@@ -99,6 +124,6 @@ pub fn get_settings(
     };
 
     let settings: SettingsDBRow =
-        rmp_serde::from_slice(&item).expect("Error deserializing settings from bytes");
+        SettingsDBRow::decode(&item).expect("Error deserializing settings from bytes");
     Ok(Some(settings))
 }
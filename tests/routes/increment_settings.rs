@@ -0,0 +1,118 @@
+use uuid::Uuid;
+
+use chrono::{Duration, Utc};
+
+use crate::helpers::{create_settings, settings_of, get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{SettingsDBRow, SettingsRevert};
+use dynamic_settings::enums::SettingsValueType;
+
+async fn increment(app: &TestApp, key: &str, body: serde_json::Value) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings/{}/increment", &app.address, key),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await
+}
+
+#[tokio::test]
+async fn test_increment_settings_concurrently() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, "0", SettingsValueType::Int));
+
+    // Act
+    let responses = futures_util::future::join_all(
+        (0..20).map(|_| increment(app, &key, serde_json::json!({"delta": 1})))
+    ).await;
+
+    // Assert
+    assert!(responses.iter().all(|response| response.status() == 200));
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "20");
+}
+
+#[tokio::test]
+async fn test_decrement_float_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, "1.5", SettingsValueType::Float));
+
+    // Act
+    let response = increment(app, &key, serde_json::json!({"delta": -0.25})).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "1.25");
+}
+
+#[tokio::test]
+async fn test_increment_settings_out_of_bounds() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, "10", SettingsValueType::Int));
+
+    // Act
+    let response = increment(app, &key, serde_json::json!({"delta": 1, "max": 10})).await;
+
+    // Assert
+    assert_eq!(response.status(), 409);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "10");
+}
+
+#[tokio::test]
+async fn test_increment_non_numeric_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, "true", SettingsValueType::Bool));
+
+    // Act
+    let response = increment(app, &key, serde_json::json!({"delta": 1})).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn test_increment_int_settings_by_float() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, "1", SettingsValueType::Int));
+
+    // Act
+    let response = increment(app, &key, serde_json::json!({"delta": 0.5})).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn test_increment_temporary_override_keeps_expiry() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    let expires_at = Utc::now() + Duration::hours(1);
+    let revert_to = SettingsRevert { value: "1".to_string(), value_type: SettingsValueType::Int };
+    create_settings(&app.partition, &SettingsDBRow {
+        expires_at: Some(expires_at),
+        revert_to: Some(revert_to.clone()),
+        ..settings_of(&key, "10", SettingsValueType::Int)
+    });
+
+    // Act
+    let response = increment(app, &key, serde_json::json!({"delta": 5})).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "15");
+    assert_eq!(settings.expires_at, Some(expires_at));
+    assert_eq!(settings.revert_to.unwrap().value, revert_to.value);
+}
@@ -0,0 +1,209 @@
+use uuid::Uuid;
+
+use crate::helpers::{create_settings, int_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::enums::DeleteOutcome;
+use dynamic_settings::models::{
+    DeleteSettingsResponse, FlagsEvaluationResponse, IntegrityReportResponse, SearchSettingsResponse, SettingsDBRow,
+    SettingsPageResponse
+};
+
+/// Current format byte followed by bytes that are not MessagePack
+const CORRUPT_VALUE: &[u8] = b"\x01\xc1garbage";
+
+/// Corrupt settings in the shared database, removed when dropped, also when the test fails,
+/// so that later runs and the other tests do not find them
+struct CorruptSettings {
+    app: &'static TestApp,
+    key: String,
+}
+
+impl Drop for CorruptSettings {
+    fn drop(&mut self) {
+        self.app.partition
+            .remove(self.key.as_str())
+            .expect("Failed to remove settings");
+    }
+}
+
+fn create_corrupt_settings(app: &'static TestApp, key: &str) -> CorruptSettings {
+    app.partition
+        .insert(key, CORRUPT_VALUE)
+        .expect("Failed to insert settings");
+    CorruptSettings { app, key: key.to_string() }
+}
+
+async fn check_integrity(app: &TestApp, api_key: &str) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/admin/integrity", &app.address),
+        api_key.to_string(),
+        None,
+        reqwest::Method::GET,
+    ).await
+}
+
+#[tokio::test]
+async fn test_integrity_reports_corrupt_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let corrupt_key = Uuid::new_v4().to_string();
+    let key = Uuid::new_v4().to_string();
+    let _corrupt = create_corrupt_settings(app, &corrupt_key);
+    create_settings(&app.partition, &int_settings(&key));
+
+    // Act
+    let response = check_integrity(app, &app.admin_api_key).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let report: IntegrityReportResponse = response.json().await.unwrap();
+    assert!(report.checked >= 2);
+    assert!(report.corrupt.iter().any(|corrupt| corrupt.key == corrupt_key));
+    assert!(!report.corrupt.iter().any(|corrupt| corrupt.key == key));
+}
+
+#[tokio::test]
+async fn test_integrity_requires_admin() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = check_integrity(app, &app.api_key).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+}
+
+#[tokio::test]
+async fn test_get_settings_skips_corrupt_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = Uuid::new_v4().to_string();
+    let key = format!("{}.valid", prefix);
+    let corrupt_key = format!("{}.corrupt", prefix);
+    create_settings(&app.partition, &int_settings(&key));
+    let _corrupt = create_corrupt_settings(app, &corrupt_key);
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings?prefix={}&limit=100", &app.address, prefix),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let page: SettingsPageResponse = response.json().await.unwrap();
+    let keys: Vec<String> = page.items.into_iter().map(|settings| settings.key).collect();
+    assert_eq!(keys, vec![key]);
+    assert_eq!(page.corrupt_keys, vec![corrupt_key]);
+}
+
+#[tokio::test]
+async fn test_get_settings_list_reports_corrupt_settings_in_header() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = Uuid::new_v4().to_string();
+    let key = format!("{}.valid", prefix);
+    let corrupt_key = format!("{}.corrupt", prefix);
+    create_settings(&app.partition, &int_settings(&key));
+    let _corrupt = create_corrupt_settings(app, &corrupt_key);
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings?prefix={}", &app.address, prefix),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let corrupt_keys: Vec<String> = serde_json::from_slice(
+        response.headers()["x-corrupt-keys"].as_bytes()
+    ).unwrap();
+    assert_eq!(corrupt_keys, vec![corrupt_key]);
+    let settings: Vec<SettingsDBRow> = response.json().await.unwrap();
+    let keys: Vec<String> = settings.into_iter().map(|settings| settings.key).collect();
+    assert_eq!(keys, vec![key]);
+}
+
+#[tokio::test]
+async fn test_search_and_evaluate_flags_report_corrupt_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = Uuid::new_v4().to_string();
+    let corrupt_key = format!("{}.corrupt", prefix);
+    create_settings(&app.partition, &int_settings(&format!("{}.valid", prefix)));
+    let _corrupt = create_corrupt_settings(app, &corrupt_key);
+
+    // Act
+    let search = make_request(
+        format!("{}/api/v1/settings/search?q={}", &app.address, prefix),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+    let evaluate = make_request(
+        format!("{}/api/v1/flags/evaluate", &app.address),
+        app.api_key.clone(),
+        Some(serde_json::json!({"prefix": prefix, "subject": "user-1"})),
+        reqwest::Method::POST,
+    ).await;
+
+    // Assert
+    assert_eq!(search.status(), 200);
+    let search: SearchSettingsResponse = search.json().await.unwrap();
+    assert_eq!(search.items.len(), 1);
+    assert!(search.corrupt_keys.contains(&corrupt_key));
+
+    assert_eq!(evaluate.status(), 200);
+    let evaluate: FlagsEvaluationResponse = evaluate.json().await.unwrap();
+    assert!(evaluate.items.is_empty());
+    assert_eq!(evaluate.corrupt_keys, vec![corrupt_key]);
+}
+
+#[tokio::test]
+async fn test_get_corrupt_settings_by_key() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    let _corrupt = create_corrupt_settings(app, &key);
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/{}", &app.address, key),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 500);
+}
+
+#[tokio::test]
+async fn test_delete_corrupt_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    let _corrupt = create_corrupt_settings(app, &key);
+
+    let body = serde_json::json!({
+        "keys": [key]
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let response: DeleteSettingsResponse = response.json().await.unwrap();
+    assert_eq!(response.results[0].outcome, DeleteOutcome::Deleted);
+    assert!(app.partition.get(&key).unwrap().is_none());
+}
@@ -0,0 +1,133 @@
+use uuid::Uuid;
+
+use crate::helpers::{create_settings, int_settings, get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{DeleteSettingsResponse, SettingsDBRow};
+use dynamic_settings::enums::DeleteOutcome;
+
+async fn set_locked(app: &TestApp, key: &str, action: &str, api_key: &str) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings/{}/{}", &app.address, key, action),
+        api_key.to_string(),
+        None,
+        reqwest::Method::POST,
+    ).await
+}
+
+#[tokio::test]
+async fn test_lock_settings_requires_admin() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    // Act
+    let response = set_locked(app, &key, "lock", &app.api_key).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert!(!settings.locked);
+}
+
+#[tokio::test]
+async fn test_update_locked_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+    let response = set_locked(app, &key, "lock", &app.admin_api_key).await;
+    assert_eq!(response.status(), 200);
+
+    let body = serde_json::json!({
+        "key": key,
+        "value": "200",
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body.clone()),
+        reqwest::Method::PUT,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "100");
+
+    // Can be updated again once unlocked
+    let response = set_locked(app, &key, "unlock", &app.admin_api_key).await;
+    assert_eq!(response.status(), 200);
+
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::PUT,
+    ).await;
+    assert_eq!(response.status(), 200);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "200");
+}
+
+#[tokio::test]
+async fn test_delete_locked_settings_skipped() {
+    // Arrange
+    let app = spawn_app().await;
+    let locked_key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &SettingsDBRow { locked: true, ..int_settings(&locked_key) });
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let body = serde_json::json!({
+        "keys": [locked_key, key]
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let response: DeleteSettingsResponse = response.json().await.unwrap();
+    let outcomes: Vec<(String, DeleteOutcome)> = response.results
+        .into_iter()
+        .map(|result| (result.key, result.outcome))
+        .collect();
+    assert_eq!(outcomes, vec![
+        (locked_key.clone(), DeleteOutcome::Locked),
+        (key.clone(), DeleteOutcome::Deleted),
+    ]);
+
+    assert!(get_settings(&app.partition, &locked_key).unwrap().is_some());
+    assert!(get_settings(&app.partition, &key).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_delete_only_locked_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &SettingsDBRow { locked: true, ..int_settings(&key) });
+
+    let body = serde_json::json!({
+        "keys": [key]
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+    assert!(get_settings(&app.partition, &key).unwrap().is_some());
+}
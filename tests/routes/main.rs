@@ -3,5 +3,21 @@ mod auth;
 mod get_settings_by_key;
 mod delete_settings;
 mod update_settings;
+mod evaluate_flag;
+mod expire_settings;
 mod health_check;
+mod reveal_settings;
+mod schedule_settings;
+mod change_requests;
+mod lock_settings;
+mod settings_metadata;
+mod search_settings;
+mod get_settings;
+mod settings_transaction;
+mod move_settings;
+mod patch_settings;
+mod increment_settings;
+mod trash;
+mod integrity;
+mod backup;
 mod helpers;
@@ -0,0 +1,130 @@
+use uuid::Uuid;
+
+use chrono::{Duration, Utc};
+
+use crate::helpers::{create_settings, int_settings, get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{MoveSettingsResponse, SettingsDBRow};
+
+/// Created a day ago with an owner and a version, to check what a move keeps
+fn create_moved_settings(app: &TestApp, key: &str) -> SettingsDBRow {
+    let created_at = Utc::now() - Duration::days(1);
+    let settings = SettingsDBRow {
+        created_at,
+        updated_at: created_at,
+        owner: Some("team-payments".to_string()),
+        version: 3,
+        ..int_settings(key)
+    };
+    create_settings(&app.partition, &settings);
+    settings
+}
+
+async fn move_settings(app: &TestApp, action: &str, body: serde_json::Value) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings/{}", &app.address, action),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await
+}
+
+#[tokio::test]
+async fn test_rename_settings_by_prefix() {
+    // Arrange
+    let app = spawn_app().await;
+    let root = Uuid::new_v4().to_string();
+    let original = create_moved_settings(app, &format!("{}.payments.timeout", root));
+    create_moved_settings(app, &format!("{}.payments.retries", root));
+
+    let body = serde_json::json!({
+        "from": format!("{}.payments.", root),
+        "to": format!("{}.billing.payments.", root),
+        "prefix": true,
+    });
+
+    // Act
+    let response = move_settings(app, "rename", body).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let response: MoveSettingsResponse = response.json().await.unwrap();
+    assert_eq!(response.keys.len(), 2);
+
+    assert!(get_settings(&app.partition, &format!("{}.payments.timeout", root)).unwrap().is_none());
+    let renamed = get_settings(&app.partition, &format!("{}.billing.payments.timeout", root))
+        .unwrap()
+        .unwrap();
+    assert_eq!(renamed.value, "100");
+    assert_eq!(renamed.owner, original.owner);
+    assert_eq!(renamed.version, original.version);
+    assert_eq!(renamed.created_at, original.created_at);
+}
+
+#[tokio::test]
+async fn test_copy_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    let copy_key = Uuid::new_v4().to_string();
+    create_moved_settings(app, &key);
+
+    let body = serde_json::json!({
+        "from": key,
+        "to": copy_key,
+    });
+
+    // Act
+    let response = move_settings(app, "copy", body).await;
+
+    // Assert
+    assert_eq!(response.status(), 201);
+    assert!(get_settings(&app.partition, &key).unwrap().is_some());
+
+    let copy = get_settings(&app.partition, &copy_key).unwrap().unwrap();
+    assert_eq!(copy.value, "100");
+    assert_eq!(copy.owner.as_deref(), Some("team-payments"));
+    assert_eq!(copy.version, 1);
+}
+
+#[tokio::test]
+async fn test_rename_settings_destination_exists() {
+    // Arrange
+    let app = spawn_app().await;
+    let root = Uuid::new_v4().to_string();
+    create_moved_settings(app, &format!("{}.a.x", root));
+    create_moved_settings(app, &format!("{}.a.y", root));
+    create_moved_settings(app, &format!("{}.b.y", root));
+
+    let body = serde_json::json!({
+        "from": format!("{}.a.", root),
+        "to": format!("{}.b.", root),
+        "prefix": true,
+    });
+
+    // Act
+    let response = move_settings(app, "rename", body).await;
+
+    // Assert
+    assert_eq!(response.status(), 409);
+    // nothing is moved
+    assert!(get_settings(&app.partition, &format!("{}.a.x", root)).unwrap().is_some());
+    assert!(get_settings(&app.partition, &format!("{}.b.x", root)).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_rename_settings_overlapping_prefixes() {
+    // Arrange
+    let app = spawn_app().await;
+
+    let body = serde_json::json!({
+        "from": "payments.",
+        "to": "payments.v2.",
+        "prefix": true,
+    });
+
+    // Act
+    let response = move_settings(app, "rename", body).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
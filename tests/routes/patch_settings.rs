@@ -0,0 +1,134 @@
+use uuid::Uuid;
+
+use chrono::{Duration, Utc};
+
+use crate::helpers::{create_settings, get_settings, settings_of, spawn_app, TestApp};
+use dynamic_settings::models::SettingsDBRow;
+use dynamic_settings::enums::SettingsValueType;
+
+async fn patch(app: &TestApp, key: &str, content_type: &str, body: serde_json::Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .patch(format!("{}/api/v1/settings/{}", &app.address, key))
+        .header("X-Api-Key", &app.api_key)
+        .header("Content-Type", content_type)
+        .body(body.to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+fn stored_json(app: &TestApp, key: &str) -> serde_json::Value {
+    let settings = get_settings(&app.partition, key).unwrap().unwrap();
+    serde_json::from_str(&settings.value).unwrap()
+}
+
+#[tokio::test]
+async fn test_json_patch_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, r#"{"hosts": ["a"], "timeout": 100}"#, SettingsValueType::Json));
+
+    let body = serde_json::json!([
+        {"op": "add", "path": "/hosts/-", "value": "b"},
+        {"op": "replace", "path": "/timeout", "value": 200},
+    ]);
+
+    // Act
+    let response = patch(app, &key, "application/json-patch+json", body).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert_eq!(stored_json(app, &key), serde_json::json!({"hosts": ["a", "b"], "timeout": 200}));
+}
+
+#[tokio::test]
+async fn test_merge_patch_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, r#"{"retries": 3, "timeout": 100}"#, SettingsValueType::Json));
+
+    let body = serde_json::json!({"retries": null, "timeout": 200});
+
+    // Act
+    let response = patch(app, &key, "application/merge-patch+json", body).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert_eq!(stored_json(app, &key), serde_json::json!({"timeout": 200}));
+}
+
+#[tokio::test]
+async fn test_patch_flag_revalidated() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, r#"{"rollout": 10}"#, SettingsValueType::Flag));
+
+    let body = serde_json::json!({"rollout": 101});
+
+    // Act
+    let response = patch(app, &key, "application/merge-patch+json", body).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+    assert_eq!(stored_json(app, &key), serde_json::json!({"rollout": 10}));
+}
+
+#[tokio::test]
+async fn test_patch_settings_failed_test_operation() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, r#"{"timeout": 100}"#, SettingsValueType::Json));
+
+    let body = serde_json::json!([
+        {"op": "test", "path": "/timeout", "value": 50},
+        {"op": "replace", "path": "/timeout", "value": 200},
+    ]);
+
+    // Act
+    let response = patch(app, &key, "application/json-patch+json", body).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+    assert_eq!(stored_json(app, &key), serde_json::json!({"timeout": 100}));
+}
+
+#[tokio::test]
+async fn test_patch_non_json_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, "100", SettingsValueType::Int));
+
+    // Act
+    let response = patch(app, &key, "application/merge-patch+json", serde_json::json!({})).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn test_patch_temporary_override_keeps_expiry() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    let expires_at = Utc::now() + Duration::hours(1);
+    create_settings(&app.partition, &SettingsDBRow {
+        expires_at: Some(expires_at),
+        ..settings_of(&key, r#"{"timeout": 100}"#, SettingsValueType::Json)
+    });
+
+    // Act
+    let response = patch(app, &key, "application/merge-patch+json", serde_json::json!({"timeout": 200})).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert_eq!(stored_json(app, &key), serde_json::json!({"timeout": 200}));
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.expires_at, Some(expires_at));
+    // without a value to revert to the settings is still deleted when it expires
+    assert!(settings.revert_to.is_none());
+}
@@ -0,0 +1,149 @@
+use uuid::Uuid;
+
+use crate::helpers::{get_settings, spawn_app, make_request};
+use dynamic_settings::models::{MessageResponse, SettingsDBRow, SECRET_MASK};
+
+async fn create_secret(key: &str, value: &str) {
+    let app = spawn_app().await;
+    let body = serde_json::json!({
+        "key": key,
+        "value": value,
+        "type": "secret"
+    });
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+    assert_eq!(response.status(), 201);
+}
+
+#[tokio::test]
+async fn test_secret_settings_encrypted_and_masked() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_secret(&key, "db-password").await;
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/{}", &app.address, key),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let body: SettingsDBRow = response.json().await.unwrap();
+    assert_eq!(body.value, SECRET_MASK);
+
+    // Encrypted at rest
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_ne!(settings.value, "db-password");
+}
+
+#[tokio::test]
+async fn test_reveal_settings_ok() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_secret(&key, "db-password").await;
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/{}/reveal", &app.address, key),
+        app.admin_api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let body: SettingsDBRow = response.json().await.unwrap();
+    assert_eq!(body.key, key);
+    assert_eq!(body.value, "db-password");
+}
+
+#[tokio::test]
+async fn test_reveal_settings_with_temporary_override() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_secret(&key, "db-password").await;
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(serde_json::json!({"key": key, "value": "temporary-password", "ttl": 3600})),
+        reqwest::Method::PUT,
+    ).await;
+    assert_eq!(response.status(), 200);
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/{}/reveal", &app.address, key),
+        app.admin_api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let body: SettingsDBRow = response.json().await.unwrap();
+    assert_eq!(body.value, "temporary-password");
+    assert_eq!(body.revert_to.unwrap().value, "db-password");
+}
+
+#[tokio::test]
+async fn test_reveal_settings_not_admin() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_secret(&key, "db-password").await;
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/{}/reveal", &app.address, key),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, "admin `X-Api-Key` is required");
+}
+
+#[tokio::test]
+async fn test_dry_run_converting_secret_not_admin() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_secret(&key, "db-password").await;
+    let body = serde_json::json!({
+        "key": key,
+        "type": "int",
+        "dryRun": true,
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body.clone()),
+        reqwest::Method::PUT,
+    ).await;
+    let admin_response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.admin_api_key.clone(),
+        Some(body),
+        reqwest::Method::PUT,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+    assert_eq!(admin_response.status(), 200);
+}
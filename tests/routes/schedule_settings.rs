@@ -0,0 +1,145 @@
+use uuid::Uuid;
+
+use chrono::{Duration, Utc};
+
+use crate::helpers::{create_settings, int_settings, get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{MessageResponse, ScheduledChangeDBRow};
+
+async fn schedule(app: &TestApp, key: &str, body: serde_json::Value) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings/{}/schedule", &app.address, key),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await
+}
+
+async fn get_scheduled(app: &TestApp, key: &str) -> Vec<ScheduledChangeDBRow> {
+    make_request(
+        format!("{}/api/v1/settings/{}/schedule", &app.address, key),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_schedule_settings_created() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let apply_at = Utc::now() + Duration::hours(1);
+    let body = serde_json::json!({
+        "value": "200",
+        "applyAt": apply_at,
+    });
+
+    // Act
+    let response = schedule(app, &key, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 201);
+
+    let scheduled = get_scheduled(app, &key).await;
+    assert_eq!(scheduled.len(), 1);
+    assert_eq!(scheduled[0].value, "200");
+    assert_eq!(scheduled[0].apply_at, apply_at);
+
+    // Not applied before `applyAt`
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "100");
+}
+
+#[tokio::test]
+async fn test_schedule_settings_invalid_value() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let body = serde_json::json!({
+        "value": "not int",
+        "applyAt": Utc::now() + Duration::hours(1),
+    });
+
+    // Act
+    let response = schedule(app, &key, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, "Value 'not int' is not a valid integer");
+    assert!(get_scheduled(app, &key).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_schedule_settings_in_the_past() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let body = serde_json::json!({
+        "value": "200",
+        "applyAt": Utc::now() - Duration::hours(1),
+    });
+
+    // Act
+    let response = schedule(app, &key, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, "`applyAt` should be in the future");
+}
+
+#[tokio::test]
+async fn test_cancel_scheduled_change() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+
+    let apply_at = Utc::now() + Duration::hours(1);
+    let body = serde_json::json!({
+        "value": "200",
+        "applyAt": apply_at,
+    });
+    let scheduled: ScheduledChangeDBRow = schedule(app, &key, body).await.json().await.unwrap();
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/{}/schedule/{}", &app.address, key, scheduled.id),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::DELETE,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert!(get_scheduled(app, &key).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_schedule_protected_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = format!("protected.{}", Uuid::new_v4());
+    create_settings(&app.partition, &int_settings(&key));
+
+    let body = serde_json::json!({
+        "value": "200",
+        "applyAt": Utc::now() + Duration::hours(1),
+    });
+
+    // Act
+    let response = schedule(app, &key, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+    assert!(get_scheduled(app, &key).await.is_empty());
+}
@@ -0,0 +1,135 @@
+use uuid::Uuid;
+
+use crate::helpers::{create_settings, settings_of, spawn_app, make_request, TestApp};
+use dynamic_settings::models::SearchSettingsResponse;
+use dynamic_settings::enums::SettingsValueType;
+
+async fn search(app: &TestApp, query: &str) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings/search?{}", &app.address, query),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await
+}
+
+#[tokio::test]
+async fn test_search_settings_exact_key_first() {
+    // Arrange
+    let app = spawn_app().await;
+    let term = Uuid::new_v4().simple().to_string();
+    // "a." sorts before the exact key, the value match sorts before both
+    create_settings(&app.partition, &settings_of(&format!("0.{}", term), "plain", SettingsValueType::Str));
+    create_settings(&app.partition, &settings_of(&format!("a.{}", term), "1", SettingsValueType::Int));
+    create_settings(&app.partition, &settings_of(&term, "2", SettingsValueType::Int));
+    let value = format!("has {} inside", term);
+    create_settings(&app.partition, &settings_of(&Uuid::new_v4().to_string(), &value, SettingsValueType::Str));
+
+    // Act
+    let response = search(app, &format!("q={}", term.to_uppercase())).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let found = response.json::<SearchSettingsResponse>().await.unwrap().items;
+    let keys: Vec<&str> = found.iter().map(|s| s.key.as_str()).collect();
+    assert_eq!(keys.len(), 4);
+    assert_eq!(keys[0], term);
+    assert_eq!(keys[1], format!("0.{}", term));
+    assert_eq!(keys[2], format!("a.{}", term));
+}
+
+#[tokio::test]
+async fn test_search_settings_exact_key_with_small_limit() {
+    // Arrange
+    let app = spawn_app().await;
+    let term = Uuid::new_v4().simple().to_string();
+    // both key matches sort before the exact key
+    create_settings(&app.partition, &settings_of(&format!("0.{}", term), "1", SettingsValueType::Int));
+    create_settings(&app.partition, &settings_of(&format!("a.{}", term), "2", SettingsValueType::Int));
+    create_settings(&app.partition, &settings_of(&term, "3", SettingsValueType::Int));
+
+    // Act
+    let response = search(app, &format!("q={}&limit=1", term)).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let found = response.json::<SearchSettingsResponse>().await.unwrap().items;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].key, term);
+}
+
+#[tokio::test]
+async fn test_search_settings_exact_key_in_other_case_after_limit() {
+    // Arrange
+    let app = spawn_app().await;
+    let term = format!("zz{}", Uuid::new_v4().simple());
+    // more key matches than the limit, all sorted before the exact key,
+    // which is stored in neither the casing of the query nor lowercased
+    for prefix in ["A", "B", "C"] {
+        create_settings(&app.partition, &settings_of(&format!("{}.{}", prefix, term), "1", SettingsValueType::Int));
+    }
+    let exact_key = format!("Zz{}", &term[2..]);
+    create_settings(&app.partition, &settings_of(&exact_key, "2", SettingsValueType::Int));
+
+    // Act
+    let response = search(app, &format!("q={}&limit=2", term.to_uppercase())).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let keys: Vec<String> = response.json::<SearchSettingsResponse>().await.unwrap()
+        .items
+        .into_iter()
+        .map(|settings| settings.key)
+        .collect();
+    assert_eq!(keys, vec![exact_key, format!("A.{}", term)]);
+}
+
+#[tokio::test]
+async fn test_search_settings_regex_type_and_limit() {
+    // Arrange
+    let app = spawn_app().await;
+    let term = Uuid::new_v4().simple().to_string();
+    create_settings(&app.partition, &settings_of(&format!("{}.timeout_ms", term), "100", SettingsValueType::Int));
+    create_settings(&app.partition, &settings_of(&format!("{}.retries", term), "3", SettingsValueType::Int));
+    create_settings(&app.partition, &settings_of(&format!("{}.name", term), "orders", SettingsValueType::Str));
+
+    // Act
+    let by_type = search(app, &format!("q={}&type=int", term))
+        .await.json::<SearchSettingsResponse>().await.unwrap().items;
+    let by_regex = search(app, &format!("q=^{}%5C.(name|retries)$&regex=true", term))
+        .await.json::<SearchSettingsResponse>().await.unwrap().items;
+    let limited = search(app, &format!("q={}&limit=1", term))
+        .await.json::<SearchSettingsResponse>().await.unwrap().items;
+
+    // Assert
+    assert_eq!(by_type.len(), 2);
+    assert_eq!(by_regex.len(), 2);
+    assert_eq!(limited.len(), 1);
+}
+
+#[tokio::test]
+async fn test_search_settings_does_not_match_secret_values() {
+    // Arrange
+    let app = spawn_app().await;
+    let term = Uuid::new_v4().simple().to_string();
+    create_settings(&app.partition, &settings_of(&Uuid::new_v4().to_string(), &term, SettingsValueType::Secret));
+
+    // Act
+    let found = search(app, &format!("q={}", term))
+        .await.json::<SearchSettingsResponse>().await.unwrap().items;
+
+    // Assert
+    assert!(found.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_settings_invalid_regex() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = search(app, "q=(&regex=true").await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
@@ -0,0 +1,143 @@
+use uuid::Uuid;
+
+use crate::helpers::{get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::SettingsDBRow;
+
+async fn create(app: &TestApp, body: serde_json::Value) {
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await;
+    assert_eq!(response.status(), 201);
+}
+
+async fn list(app: &TestApp, query: &str) -> Vec<SettingsDBRow> {
+    let response = make_request(
+        format!("{}/api/v1/settings?{}", &app.address, query),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_create_settings_with_metadata() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = Uuid::new_v4().to_string();
+    let key = format!("{}.shard_map", prefix);
+
+    // Act
+    create(app, serde_json::json!({
+        "key": key,
+        "value": "{}",
+        "type": "json",
+        "description": "Shards of the orders database",
+        "owner": "team-storage",
+        "tags": ["database", " database ", "critical"],
+    })).await;
+
+    // Assert
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.description.as_deref(), Some("Shards of the orders database"));
+    assert_eq!(settings.owner.as_deref(), Some("team-storage"));
+    assert_eq!(settings.tags, vec!["database", "critical"]);
+}
+
+#[tokio::test]
+async fn test_get_settings_filtered_by_tag_and_owner() {
+    // Arrange
+    let app = spawn_app().await;
+    let prefix = Uuid::new_v4().to_string();
+    create(app, serde_json::json!({
+        "key": format!("{}.a", prefix),
+        "value": "1",
+        "type": "int",
+        "owner": "team-a",
+        "tags": ["billing"],
+    })).await;
+    create(app, serde_json::json!({
+        "key": format!("{}.b", prefix),
+        "value": "2",
+        "type": "int",
+        "owner": "team-b",
+        "tags": ["billing", "checkout"],
+    })).await;
+
+    // Act
+    let by_tag = list(app, &format!("prefix={}&tag=billing", prefix)).await;
+    let by_owner = list(app, &format!("prefix={}&owner=team-b", prefix)).await;
+    let by_both = list(app, &format!("prefix={}&tag=checkout&owner=team-a", prefix)).await;
+
+    // Assert
+    assert_eq!(by_tag.len(), 2);
+    assert_eq!(by_owner.len(), 1);
+    assert_eq!(by_owner[0].key, format!("{}.b", prefix));
+    assert!(by_both.is_empty());
+}
+
+#[tokio::test]
+async fn test_update_settings_metadata() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create(app, serde_json::json!({
+        "key": key,
+        "value": "100",
+        "type": "int",
+        "description": "Requests per second",
+        "tags": ["limits"],
+    })).await;
+
+    let body = serde_json::json!({
+        "owner": "team-api",
+        "description": "",
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/{}/metadata", &app.address, key),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::PUT,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "100");
+    assert_eq!(settings.owner.as_deref(), Some("team-api"));
+    assert!(settings.description.is_none());
+    assert_eq!(settings.tags, vec!["limits"]);
+}
+
+#[tokio::test]
+async fn test_update_settings_metadata_empty_tag() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create(app, serde_json::json!({
+        "key": key,
+        "value": "100",
+        "type": "int",
+    })).await;
+
+    let body = serde_json::json!({
+        "tags": [" "],
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings/{}/metadata", &app.address, key),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::PUT,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
@@ -0,0 +1,113 @@
+use uuid::Uuid;
+
+use crate::helpers::{create_settings, int_settings, get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::{SettingsDBRow, SettingsTransactionResponse};
+
+async fn run_transaction(app: &TestApp, body: serde_json::Value) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings/transaction", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::POST,
+    ).await
+}
+
+#[tokio::test]
+async fn test_settings_transaction_applied() {
+    // Arrange
+    let app = spawn_app().await;
+    let updated_key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &SettingsDBRow { version: 3, ..int_settings(&updated_key) });
+    let deleted_key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &SettingsDBRow { version: 1, ..int_settings(&deleted_key) });
+    let created_key = Uuid::new_v4().to_string();
+
+    let body = serde_json::json!({
+        "operations": [
+            {"op": "create", "key": created_key, "value": "https://api.example.com", "type": "str"},
+            {"op": "update", "key": updated_key, "value": "200"},
+            {"op": "delete", "key": deleted_key},
+        ],
+        "expectedVersions": {updated_key.clone(): 3},
+    });
+
+    // Act
+    let response = run_transaction(app, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let response: SettingsTransactionResponse = response.json().await.unwrap();
+    let versions: Vec<Option<u64>> = response.results.iter().map(|r| r.version).collect();
+    assert_eq!(versions, vec![Some(1), Some(4), None]);
+
+    assert!(get_settings(&app.partition, &created_key).unwrap().is_some());
+    assert_eq!(get_settings(&app.partition, &updated_key).unwrap().unwrap().value, "200");
+    assert!(get_settings(&app.partition, &deleted_key).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_settings_transaction_version_conflict() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &SettingsDBRow { version: 2, ..int_settings(&key) });
+    let created_key = Uuid::new_v4().to_string();
+
+    let body = serde_json::json!({
+        "operations": [
+            {"op": "create", "key": created_key, "value": "1", "type": "int"},
+            {"op": "update", "key": key, "value": "200"},
+        ],
+        "expectedVersions": {key.clone(): 1},
+    });
+
+    // Act
+    let response = run_transaction(app, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 409);
+    assert!(get_settings(&app.partition, &created_key).unwrap().is_none());
+    assert_eq!(get_settings(&app.partition, &key).unwrap().unwrap().value, "100");
+}
+
+#[tokio::test]
+async fn test_settings_transaction_rolled_back_on_failed_operation() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &SettingsDBRow { version: 1, ..int_settings(&key) });
+
+    let body = serde_json::json!({
+        "operations": [
+            {"op": "update", "key": key, "value": "200"},
+            {"op": "delete", "key": Uuid::new_v4().to_string()},
+        ],
+    });
+
+    // Act
+    let response = run_transaction(app, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 404);
+    assert_eq!(get_settings(&app.partition, &key).unwrap().unwrap().value, "100");
+}
+
+#[tokio::test]
+async fn test_settings_transaction_invalid_operation() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &SettingsDBRow { version: 1, ..int_settings(&key) });
+
+    let body = serde_json::json!({
+        "operations": [
+            {"op": "update", "key": key, "value": "not a number"},
+        ],
+    });
+
+    // Act
+    let response = run_transaction(app, body).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+}
@@ -0,0 +1,97 @@
+use uuid::Uuid;
+
+use chrono::Utc;
+
+use crate::helpers::{create_settings, int_settings, settings_of, get_settings, spawn_app, make_request, TestApp};
+use dynamic_settings::models::TrashedSettingsDBRow;
+use dynamic_settings::enums::SettingsValueType;
+
+async fn delete(app: &TestApp, key: &str) {
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(serde_json::json!({"keys": [key]})),
+        reqwest::Method::DELETE,
+    ).await;
+    assert_eq!(response.status(), 200);
+}
+
+async fn restore(app: &TestApp, key: &str) -> reqwest::Response {
+    make_request(
+        format!("{}/api/v1/settings/trash/restore", &app.address),
+        app.api_key.clone(),
+        Some(serde_json::json!({"keys": [key]})),
+        reqwest::Method::POST,
+    ).await
+}
+
+async fn get_trash(app: &TestApp) -> Vec<TrashedSettingsDBRow> {
+    make_request(
+        format!("{}/api/v1/settings/trash", &app.address),
+        app.api_key.clone(),
+        None,
+        reqwest::Method::GET,
+    ).await.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_deleted_settings_restored() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &int_settings(&key));
+    delete(app, &key).await;
+
+    let trash = get_trash(app).await;
+    assert!(trash.iter().any(|trashed| trashed.settings.key == key));
+
+    // Act
+    let response = restore(app, &key).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "100");
+
+    let trash = get_trash(app).await;
+    assert!(!trash.iter().any(|trashed| trashed.settings.key == key));
+}
+
+#[tokio::test]
+async fn test_restore_protected_settings() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = format!("protected.{}", Uuid::new_v4());
+    let trashed = TrashedSettingsDBRow {
+        settings: int_settings(&key),
+        deleted_at: Utc::now(),
+    };
+    let serialized: Vec<u8> = (&trashed).into();
+    app.db.trash.insert(&key, serialized).expect("Failed to insert trashed settings");
+
+    // Act
+    let response = restore(app, &key).await;
+
+    // Assert
+    assert_eq!(response.status(), 403);
+    assert!(get_settings(&app.partition, &key).unwrap().is_none());
+    assert!(get_trash(app).await.iter().any(|trashed| trashed.settings.key == key));
+}
+
+#[tokio::test]
+async fn test_restore_settings_created_again() {
+    // Arrange
+    let app = spawn_app().await;
+    let key = Uuid::new_v4().to_string();
+    create_settings(&app.partition, &settings_of(&key, "1", SettingsValueType::Int));
+    delete(app, &key).await;
+    create_settings(&app.partition, &settings_of(&key, "2", SettingsValueType::Int));
+
+    // Act
+    let response = restore(app, &key).await;
+
+    // Assert
+    assert_eq!(response.status(), 409);
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    assert_eq!(settings.value, "2");
+}
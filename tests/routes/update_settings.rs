@@ -2,8 +2,8 @@ use uuid::Uuid;
 
 use chrono::Utc;
 
-use crate::helpers::{create_settings, spawn_app, make_request, get_settings};
-use dynamic_settings::models::{MessageResponse, SettingsDBRow};
+use crate::helpers::{create_settings, int_settings, settings_of, spawn_app, make_request, get_settings};
+use dynamic_settings::models::{MessageResponse, SettingsDBRow, UpdateSettingsDryRunResponse};
 use dynamic_settings::enums::SettingsValueType;
 
 #[tokio::test]
@@ -20,6 +20,7 @@ async fn test_update_settings_ok() {
         value_type: SettingsValueType::Int,
         created_at: now,
         updated_at: now,
+        ..Default::default()
     };
     create_settings(&app.partition, &settings);
     let body = serde_json::json!({
@@ -89,6 +90,7 @@ async fn test_update_settings_invalid_input_invalid_type() {
         value_type: SettingsValueType::Int,
         created_at: now,
         updated_at: now,
+        ..Default::default()
     };
     create_settings(&app.partition, &settings);
     let body = serde_json::json!({
@@ -114,4 +116,117 @@ async fn test_update_settings_invalid_input_invalid_type() {
     // No changes
     assert_eq!(settings.value, "100");
     assert_eq!(settings.created_at, now);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_update_settings_change_type_ok() {
+    // Arrange
+    let app = spawn_app().await;
+
+    let key = Uuid::new_v4().to_string();
+
+    let settings = settings_of(&key, "100", SettingsValueType::Str);
+    create_settings(&app.partition, &settings);
+    let body = serde_json::json!({
+        "key": key,
+        "type": "int",
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::PUT,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 200);
+
+    let updated = get_settings(&app.partition, &key).unwrap().unwrap();
+    // Type changed, value and creation time preserved
+    assert_eq!(updated.value_type, SettingsValueType::Int);
+    assert_eq!(updated.value, "100");
+    assert_eq!(updated.created_at, settings.created_at);
+}
+
+#[tokio::test]
+async fn test_update_settings_change_type_invalid_value() {
+    // Arrange
+    let app = spawn_app().await;
+
+    let key = Uuid::new_v4().to_string();
+
+    let settings = int_settings(&key);
+    create_settings(&app.partition, &settings);
+    let body = serde_json::json!({
+        "key": key,
+        "value": "not a bool",
+        "type": "bool",
+    });
+
+    // Act
+    let response = make_request(
+        format!("{}/api/v1/settings", &app.address),
+        app.api_key.clone(),
+        Some(body),
+        reqwest::Method::PUT,
+    ).await;
+
+    // Assert
+    assert_eq!(response.status(), 422);
+
+    let body: MessageResponse = response.json().await.unwrap();
+    assert_eq!(body.message, "Value 'not a bool' is not a valid boolean");
+
+    let settings = get_settings(&app.partition, &key).unwrap().unwrap();
+    // No changes
+    assert_eq!(settings.value_type, SettingsValueType::Int);
+    assert_eq!(settings.value, "100");
+}
+
+#[tokio::test]
+async fn test_update_settings_change_type_dry_run() {
+    // Arrange
+    let app = spawn_app().await;
+
+    let key = Uuid::new_v4().to_string();
+
+    let settings = settings_of(&key, "100.5", SettingsValueType::Str);
+    create_settings(&app.partition, &settings);
+
+    // TODO: use some kind of `parameterized` here
+    let test_cases = vec![
+        ("float", true),
+        ("int", false),
+    ];
+
+    for (value_type, convertible) in test_cases {
+        let body = serde_json::json!({
+            "key": key,
+            "type": value_type,
+            "dryRun": true,
+        });
+
+        // Act
+        let response = make_request(
+            format!("{}/api/v1/settings", &app.address),
+            app.api_key.clone(),
+            Some(body),
+            reqwest::Method::PUT,
+        ).await;
+
+        // Assert
+        assert_eq!(response.status(), 200);
+
+        let body: UpdateSettingsDryRunResponse = response.json().await.unwrap();
+        assert_eq!(body.from, SettingsValueType::Str);
+        assert_eq!(body.to.to_string(), value_type);
+        assert_eq!(body.convertible, convertible);
+    }
+
+    let updated = get_settings(&app.partition, &key).unwrap().unwrap();
+    // No changes
+    assert_eq!(updated.value_type, SettingsValueType::Str);
+    assert_eq!(updated.updated_at, settings.updated_at);
+}
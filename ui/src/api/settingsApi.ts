@@ -1,4 +1,6 @@
-import {CreateSettings, Settings} from "../types/settings";
+import {CreateSettings, Settings, SettingsPage} from "../types/settings";
+
+const FETCH_SETTINGS_PAGE_LIMIT = 1000;
 
 const API_BASE_URL = import.meta.env.VITE_API_API_URL;
 
@@ -15,16 +17,31 @@ const getAuthHeader = () => {
 }
 
 export const fetchSettings = async (): Promise<Settings[]> => {
-  const response = await fetch(`${API_BASE_URL}/settings`, {
-    method: "GET",
-    headers: getAuthHeader(),
-  });
+  const settings: Settings[] = [];
+  let cursor: string | null = null;
 
-  if (!response.ok) {
-    throw new Error("Failed to fetch settings");
-  }
+  do {
+    // sorted by key every page is a range scan, the list orders the settings by the last update itself
+    const params = new URLSearchParams({ limit: String(FETCH_SETTINGS_PAGE_LIMIT), sort: "key" });
+    if (cursor) {
+      params.set("cursor", cursor);
+    }
 
-  return response.json();
+    const response = await fetch(`${API_BASE_URL}/settings?${params}`, {
+      method: "GET",
+      headers: getAuthHeader(),
+    });
+
+    if (!response.ok) {
+      throw new Error("Failed to fetch settings");
+    }
+
+    const page: SettingsPage = await response.json();
+    settings.push(...page.items);
+    cursor = page.nextCursor;
+  } while (cursor);
+
+  return settings;
 };
 
 export const fetchSettingByKey = async (key: string): Promise<Settings> => {
@@ -64,7 +64,26 @@ const ValueInput: React.FC<ValueInputProps> = ({
           />
         );
 
+      case SettingsValueType.Secret:
+        // secret values are masked by the API, so the field is never prefilled with the real value
+        return (
+          <input
+            type="password"
+            value={value}
+            placeholder={disabled ? "********" : "Enter a new secret value"}
+            onChange={(e) => onChange(e.target.value)}
+            disabled={disabled}
+            autoComplete="new-password"
+            className={`w-full p-2 border border-gray-300 rounded-md bg-gray-100 mb-4 ${
+              disabled 
+                ? 'cursor-not-allowed opacity-75' 
+                : 'focus:outline-none focus:ring-2 focus:ring-blue-400'
+            }`}
+          />
+        );
+
       case SettingsValueType.Json:
+      case SettingsValueType.Flag:
         return (
           <div className="mb-4">
             <AceEditor
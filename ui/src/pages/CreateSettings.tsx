@@ -22,6 +22,8 @@ const CreateSettingsPage: React.FC = () => {
       setValue(boolValue ? "true" : "false");
     } else if (type === SettingsValueType.Json) {
       setValue("{}");
+    } else if (type === SettingsValueType.Flag) {
+      setValue('{"rollout": 0}');
     } else if (type === SettingsValueType.Int) {
       setValue("0");
     } else if (type === SettingsValueType.Float) {
@@ -59,7 +61,7 @@ const CreateSettingsPage: React.FC = () => {
     }
   };
 
-  const isSaveDisabled = key.trim() === "" || value.trim() === "" || (type === SettingsValueType.Json && jsonError !== null);
+  const isSaveDisabled = key.trim() === "" || value.trim() === "" || ((type === SettingsValueType.Json || type === SettingsValueType.Flag) && jsonError !== null);
   return (
     <div className="max-w-5xl mx-auto p-6 bg-white rounded-lg shadow-md">
       {/* Top Bar: Back Button & Title */}
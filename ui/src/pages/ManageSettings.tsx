@@ -1,6 +1,6 @@
 import { useEffect, useState } from "react";
 import { useNavigate, useParams } from "react-router-dom";
-import { Settings } from "../types/settings";
+import { Settings, SettingsValueType } from "../types/settings";
 import { fetchSettingByKey, deleteSettingByKey, updateSetting } from "../api/settingsApi";
 import KeyInput from "../components/KeyInput.tsx";
 import TypeInput from '../components/TypeInput';
@@ -47,8 +47,11 @@ const ManageSettingsPage: React.FC = () => {
     }
   };
 
+  // an empty value would replace the secret, which is never shown, with an empty one
+  const isUpdateDisabled = setting?.type === SettingsValueType.Secret && updatedValue === "";
+
   const handleUpdate = async () => {
-    if (!key) return;
+    if (!key || isUpdateDisabled) return;
     try {
       await updateSetting(key, updatedValue);
       // After update, re-fetch the setting to reflect changes
@@ -119,7 +122,12 @@ const ManageSettingsPage: React.FC = () => {
             <div className="flex space-x-4">
               <button
                 onClick={handleUpdate}
-                className="flex-1 bg-green-500 text-white px-4 py-2 rounded-md shadow hover:bg-green-600 transition"
+                disabled={isUpdateDisabled}
+                className={`flex-1 px-4 py-2 rounded-md shadow transition ${
+                  isUpdateDisabled
+                    ? "bg-gray-400 cursor-not-allowed"
+                    : "bg-green-500 text-white hover:bg-green-600"
+                }`}
               >
                 Update
               </button>
@@ -132,7 +140,13 @@ const ManageSettingsPage: React.FC = () => {
             </div>
           ) : (
             <button
-              onClick={() => setIsEditing(true)}
+              onClick={() => {
+                // do not send the masked value back as a new secret
+                if (setting.type === SettingsValueType.Secret) {
+                  setUpdatedValue("");
+                }
+                setIsEditing(true);
+              }}
               className="w-full bg-blue-500 text-white px-4 py-2 rounded-md shadow hover:bg-blue-600 transition"
             >
               Edit
@@ -3,7 +3,9 @@ export enum SettingsValueType {
     Int = "int",
     Float = "float",
     Bool = "bool",
-    Json = "json"
+    Json = "json",
+    Secret = "secret",
+    Flag = "flag"
 }
 
 export interface Settings {
@@ -11,11 +13,25 @@ export interface Settings {
     value: string
     type: SettingsValueType,
     createdAt: Date,
-    updatedAt: Date
+    updatedAt: Date,
+    locked: boolean,
+    description?: string,
+    owner?: string,
+    tags: string[],
+    version: number
+}
+
+export interface SettingsPage {
+    items: Settings[],
+    nextCursor: string | null,
+    corruptKeys?: string[]
 }
 
 export interface CreateSettings {
     key: string
     value: string
     type: SettingsValueType,
+    description?: string,
+    owner?: string,
+    tags?: string[]
 }
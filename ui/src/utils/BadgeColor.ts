@@ -10,6 +10,10 @@ export const GetBadgeColor = (type: string): string => {
       return "bg-indigo-500";
     case "json":
       return "bg-yellow-500 text-black";
+    case "secret":
+      return "bg-red-500";
+    case "flag":
+      return "bg-teal-500";
     default:
       return "bg-gray-500";
   }